@@ -0,0 +1,61 @@
+//! Measures the throughput of the parts of the front end that exist so far.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use lifesharp::ast::{self, BinaryOperation, BinaryOperator, Expression, Located};
+use lifesharp::identifier::Id;
+use lifesharp::location::FileId;
+use lifesharp::{lexer, print};
+
+/// Builds a source file containing only punctuation and operators, since the lexer does not yet handle identifiers,
+/// literals, or whitespace.
+fn punctuation_corpus(lines: usize) -> String {
+    let mut source = String::new();
+    for _ in 0..lines {
+        source.push_str("{()}[<>]\\+-*;/....=..=&|\n");
+    }
+    source
+}
+
+/// Builds a tree of functions whose bodies are long chains of binary operations.
+fn operation_tree(functions: usize) -> ast::Tree<'static> {
+    let name = |name| Located::dummy(Id::new(name).unwrap());
+    let mut tree = ast::Tree::default();
+
+    for _ in 0..functions {
+        let mut body = Expression::Name(name("a"));
+        for operator in [BinaryOperator::Add, BinaryOperator::Multiply].repeat(16) {
+            body = Expression::BinaryOperation(Box::new(BinaryOperation::new(
+                body,
+                Located::dummy(operator),
+                Expression::Name(name("b")),
+            )));
+        }
+
+        let mut definition = ast::FunctionDefinition::new(name("f"));
+        definition.body.push(Located::dummy(body));
+        tree.declarations.push(definition.into());
+    }
+
+    tree
+}
+
+fn lexer(c: &mut Criterion) {
+    let source = punctuation_corpus(10_000);
+    let mut group = c.benchmark_group("lexer");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function("punctuation", |b| {
+        b.iter(|| lexer::tokenize(source.as_str(), FileId::default(), None).unwrap())
+    });
+    group.finish();
+}
+
+fn printer(c: &mut Criterion) {
+    let tree = operation_tree(1_000);
+    let mut group = c.benchmark_group("printer");
+    group.throughput(Throughput::Elements(tree.declarations.len() as u64));
+    group.bench_function("operations", |b| b.iter(|| print::to_string(&tree)));
+    group.finish();
+}
+
+criterion_group!(benches, lexer, printer);
+criterion_main!(benches);
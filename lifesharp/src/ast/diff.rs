@@ -0,0 +1,149 @@
+//! Comparison of the declarations in two versions of a source file.
+
+use crate::ast::{FunctionDefinition, TopDeclaration, Tree};
+use crate::identifier::Identifier;
+use crate::prelude::*;
+
+/// Describes how a declaration differs between two versions of a source file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Change {
+    /// A declaration was added.
+    Added(Identifier),
+    /// A declaration was removed.
+    Removed(Identifier),
+    /// The signature of a declaration changed, such as the types of a function's parameters.
+    Modified(Identifier),
+    /// Only the body of a function changed.
+    BodyChanged(Identifier),
+}
+
+/// The parts of a function definition that other declarations depend on, printed so that locations are ignored.
+fn signature(definition: &FunctionDefinition<'_>) -> String {
+    let mut signature = String::new();
+
+    for parameter in definition.generic_parameters.iter() {
+        signature.push_str(&parameter.to_string());
+        signature.push(',');
+    }
+
+    signature.push('|');
+
+    for parameter in definition.parameters.iter() {
+        signature.push_str(&parameter.to_string());
+    }
+
+    if let Some(return_type) = &definition.return_type {
+        signature.push('|');
+        signature.push_str(&return_type.to_string());
+    }
+
+    signature
+}
+
+fn body(definition: &FunctionDefinition<'_>) -> Vec<String> {
+    definition.body.iter().map(ToString::to_string).collect()
+}
+
+fn compare(old: &TopDeclaration<'_>, new: &TopDeclaration<'_>) -> Option<Change> {
+    let name = new.name().content.to_identifier();
+
+    match (old, new) {
+        (TopDeclaration::FunctionDefinition(old), TopDeclaration::FunctionDefinition(new)) => {
+            if signature(old) != signature(new) {
+                Some(Change::Modified(name))
+            } else if body(old) != body(new) {
+                Some(Change::BodyChanged(name))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Compares the declarations of two versions of a source file.
+///
+/// Declarations are matched by name, and locations are ignored, so moving a declaration or changing the whitespace around
+/// it is not reported as a change. Removed declarations are listed first, followed by the added or modified declarations
+/// in the order that they appear in the `new` tree.
+pub fn diff(old: &Tree<'_>, new: &Tree<'_>) -> Vec<Change> {
+    let mut unmatched = old.declarations.iter().map(Some).collect::<Vec<_>>();
+    let mut added_or_modified = Vec::new();
+
+    for declaration in new.declarations.iter() {
+        let name = declaration.name().content;
+        let matching = unmatched.iter_mut().find(|old| {
+            old.map(|old| old.name().content == name)
+                .unwrap_or_default()
+        });
+
+        match matching.and_then(Option::take) {
+            Some(old) => added_or_modified.extend(compare(old, declaration)),
+            None => added_or_modified.push(Change::Added(name.to_identifier())),
+        }
+    }
+
+    unmatched
+        .into_iter()
+        .flatten()
+        .map(|old| Change::Removed(old.name().content.to_identifier()))
+        .chain(added_or_modified)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{self, Change, Expression, FunctionDefinition, Located, Tree};
+    use crate::identifier::Id;
+    use crate::location::{FileId, Span};
+    use crate::types::Primitive;
+
+    fn function(name: &str, body: bool) -> FunctionDefinition<'_> {
+        let mut definition = FunctionDefinition::new(Located::dummy(Id::new(name).unwrap()));
+        definition
+            .body
+            .push(Located::dummy(Expression::BooleanLiteral(body)));
+        definition
+    }
+
+    fn tree(definitions: Vec<FunctionDefinition<'_>>) -> Tree<'_> {
+        Tree {
+            declarations: definitions.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn name(name: &str) -> crate::identifier::Identifier {
+        Id::new(name).unwrap().to_identifier()
+    }
+
+    #[test]
+    fn unchanged_tree_has_no_changes() {
+        let mut moved = function("a", true);
+        moved.name.location = Span::new(FileId::default(), 10..11);
+        let old = tree(vec![function("a", true), function("b", false)]);
+        let new = tree(vec![function("b", false), moved]);
+        assert!(ast::diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn changes_are_reported() {
+        let mut modified = function("c", true);
+        modified.return_type = Some(Located::dummy(ast::Type::Primitive(Primitive::Bool)));
+        let old = tree(vec![
+            function("a", true),
+            function("b", true),
+            function("c", true),
+        ]);
+        let new = tree(vec![function("d", true), function("b", false), modified]);
+
+        assert_eq!(
+            vec![
+                Change::Removed(name("a")),
+                Change::Added(name("d")),
+                Change::BodyChanged(name("b")),
+                Change::Modified(name("c")),
+            ],
+            ast::diff(&old, &new)
+        );
+    }
+}
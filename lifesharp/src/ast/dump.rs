@@ -0,0 +1,295 @@
+//! Writes a readable outline of the nodes in a syntax tree, used when debugging the compiler.
+
+use crate::ast::{
+    Block, Expression, FunctionDefinition, GenericParameterKind, Id, Located, Parameter, Pattern,
+    TopDeclaration, Tree, Type,
+};
+use crate::location::{SourceMap, Span};
+use crate::prelude::*;
+use core::fmt::Write as _;
+
+struct Dumper<'m> {
+    output: String,
+    indent_level: usize,
+    source_map: &'m SourceMap,
+}
+
+impl Dumper<'_> {
+    /// Writes a line describing a node, along with its span.
+    fn node(&mut self, description: &str, span: Option<Span>) {
+        for _ in 0..self.indent_level {
+            self.output.push_str("  ");
+        }
+
+        self.output.push_str(description);
+
+        match span.map(|span| (span, self.source_map.span_locations(span))) {
+            Some((_, Some((start, end)))) => {
+                let _ = write!(
+                    self.output,
+                    " {}:{}-{}:{}",
+                    start.line_number(),
+                    start.column_number(),
+                    end.line_number(),
+                    end.column_number()
+                );
+            }
+            Some((span, None)) if span.is_synthetic() => self.output.push_str(" <synthetic>"),
+            Some((span, None)) => {
+                let _ = write!(self.output, " @{}..{}", span.start(), span.end());
+            }
+            None => (),
+        }
+
+        self.output.push('\n');
+    }
+
+    fn nested<F: FnOnce(&mut Self)>(&mut self, description: &str, span: Option<Span>, f: F) {
+        self.node(description, span);
+        self.indent_level += 1;
+        f(self);
+        self.indent_level -= 1;
+    }
+
+    fn name(&mut self, kind: &str, name: &Id<'_>) {
+        self.node(
+            &format!("{} {:?}", kind, name.content.as_str()),
+            Some(name.location),
+        );
+    }
+
+    fn type_name(&mut self, kind: &str, type_name: &Located<Type<'_>>) {
+        self.node(
+            &format!("{} {}", kind, type_name.content),
+            Some(type_name.location),
+        );
+    }
+
+    fn pattern(&mut self, pattern: &Pattern<'_>) {
+        match pattern {
+            Pattern::Name(name) => self.name("NamePattern", name),
+            Pattern::Ignore => self.node("IgnorePattern", None),
+            Pattern::Range(range) => self.nested("RangePattern", None, |dumper| {
+                dumper.expression(&range.start, None);
+                dumper.expression(&range.end, None);
+            }),
+            Pattern::Record(fields) => self.nested("RecordPattern", None, |dumper| {
+                for field in fields.iter() {
+                    dumper.nested("Field", Some(field.location), |dumper| {
+                        dumper.name("Name", &field.content.name);
+                        dumper.pattern(&field.content.pattern);
+                    });
+                }
+            }),
+            Pattern::UnionCase(case) => self.nested("UnionCasePattern", None, |dumper| {
+                dumper.name("Case", &case.case);
+                for argument in case.arguments.iter() {
+                    dumper.pattern(argument);
+                }
+            }),
+        }
+    }
+
+    fn block(&mut self, kind: &str, block: &Block<'_>) {
+        self.nested(kind, None, |dumper| {
+            for expression in block.iter() {
+                dumper.expression(&expression.content, Some(expression.location));
+            }
+        })
+    }
+
+    fn expression(&mut self, expression: &Expression<'_>, span: Option<Span>) {
+        match expression {
+            Expression::BooleanLiteral(value) => {
+                self.node(&format!("BooleanLiteral {}", value), span)
+            }
+            Expression::Name(name) => self.name("Name", name),
+            Expression::Error => self.node("Error", span),
+            Expression::IfElse(conditional) => self.nested("IfElse", span, |dumper| {
+                dumper.expression(&conditional.condition, None);
+                dumper.block("Then", &conditional.true_branch);
+
+                for (condition, branch) in conditional.other_branches.iter() {
+                    dumper.nested("Elif", None, |dumper| {
+                        dumper.expression(condition, None);
+                        dumper.block("Then", branch);
+                    });
+                }
+
+                dumper.block("Else", &conditional.else_branch);
+            }),
+            Expression::BinaryOperation(operation) => self.nested(
+                &format!("BinaryOperation {:?}", operation.operator.content),
+                span,
+                |dumper| {
+                    dumper.expression(&operation.left, None);
+                    dumper.expression(&operation.right, None);
+                },
+            ),
+            Expression::UnaryOperation(operation) => self.nested(
+                &format!("UnaryOperation {:?}", operation.operator.content),
+                span,
+                |dumper| dumper.expression(&operation.operand, None),
+            ),
+            Expression::Range(range) => {
+                let kind = if range.inclusive {
+                    "InclusiveRange"
+                } else {
+                    "Range"
+                };
+                self.nested(kind, span, |dumper| {
+                    dumper.expression(&range.start, None);
+                    dumper.expression(&range.end, None);
+                })
+            }
+            Expression::Block(block) => self.block("Block", block),
+            Expression::Unsafe(block) => self.block("Unsafe", block),
+            Expression::Parenthesized(inner) => self.nested("Parenthesized", span, |dumper| {
+                dumper.expression(&inner.content, Some(inner.location))
+            }),
+        }
+    }
+
+    fn parameter(&mut self, parameter: &Located<Parameter<'_>>) {
+        let span = Some(parameter.location);
+        let parameter = &parameter.content;
+        self.nested("Parameter", span, |dumper| {
+            dumper.pattern(&parameter.pattern);
+            dumper.type_name("Type", &parameter.argument_type);
+        })
+    }
+
+    fn function_definition(&mut self, definition: &FunctionDefinition<'_>) {
+        self.nested("FunctionDefinition", None, |dumper| {
+            dumper.name("Name", &definition.name);
+
+            if definition.is_unsafe {
+                dumper.node("Unsafe", None);
+            }
+
+            for Located {
+                content: parameter,
+                location,
+            } in definition.generic_parameters.iter()
+            {
+                match &parameter.kind {
+                    GenericParameterKind::Type(constraints) => {
+                        dumper.nested("GenericTypeParameter", Some(*location), |dumper| {
+                            dumper.name("Name", &parameter.name);
+                            for Located { content, location } in constraints.iter() {
+                                dumper.node(&format!("Constraint {}", content), Some(*location));
+                            }
+                        })
+                    }
+                    GenericParameterKind::Lifetime(()) => {
+                        dumper.name("GenericLifetimeParameter", &parameter.name)
+                    }
+                }
+            }
+
+            for parameter in definition.parameters.iter() {
+                dumper.parameter(parameter);
+            }
+
+            if let Some(return_type) = &definition.return_type {
+                dumper.type_name("ReturnType", return_type);
+            }
+
+            dumper.block("Body", &definition.body);
+        })
+    }
+}
+
+/// Writes each node of the tree on its own line, indented according to its depth, along with the range of line and
+/// column numbers covered by the nodes that have a location.
+///
+/// Nodes generated by the compiler are marked as `<synthetic>`, and spans in files missing from the `source_map` are
+/// written as byte offsets.
+pub fn dump(tree: &Tree<'_>, source_map: &SourceMap) -> String {
+    let mut dumper = Dumper {
+        output: String::new(),
+        indent_level: 0,
+        source_map,
+    };
+
+    dumper.nested("Tree", None, |dumper| {
+        for declaration in tree.declarations.iter() {
+            match declaration {
+                TopDeclaration::FunctionDefinition(definition) => {
+                    dumper.function_definition(definition)
+                }
+            }
+        }
+    });
+
+    dumper.output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{
+        self, BinaryOperation, BinaryOperator, Expression, FunctionDefinition, Located, Parameter,
+        Pattern, PrimitiveType, Type,
+    };
+    use crate::identifier::Id;
+    use crate::location::{SourceMap, Span};
+
+    #[test]
+    fn nodes_are_written_with_spans() {
+        let mut source_map = SourceMap::default();
+        let file = source_map.add("test.lfs", "def f () =\n    a + true\n");
+
+        let mut definition =
+            FunctionDefinition::new(Located::new(Id::new("f").unwrap(), Span::new(file, 4..5)));
+        definition.body.push(Located::new(
+            Expression::BinaryOperation(Box::new(BinaryOperation::new(
+                Expression::Name(Located::new(Id::new("a").unwrap(), Span::new(file, 15..16))),
+                Located::new(BinaryOperator::Add, Span::new(file, 17..18)),
+                Expression::BooleanLiteral(true),
+            ))),
+            Span::new(file, 15..23),
+        ));
+
+        let tree = ast::Tree {
+            declarations: vec![definition.into()],
+        };
+
+        assert_eq!(
+            "Tree\n  FunctionDefinition\n    Name \"f\" 1:5-1:6\n    Body\n      BinaryOperation Add 2:5-2:13\n        \
+             Name \"a\" 2:5-2:6\n        BooleanLiteral true\n",
+            ast::dump(&tree, &source_map)
+        );
+    }
+
+    #[test]
+    fn parameters_and_types_are_written_with_spans() {
+        let mut source_map = SourceMap::default();
+        let file = source_map.add("test.lfs", "def f (x: u8) bool =\n    x\n");
+
+        let mut definition =
+            FunctionDefinition::new(Located::new(Id::new("f").unwrap(), Span::new(file, 4..5)));
+        let mut parameter = Parameter::new(Located::new(
+            Type::Primitive(PrimitiveType::U8),
+            Span::new(file, 10..12),
+        ));
+        parameter.pattern =
+            Pattern::Name(Located::new(Id::new("x").unwrap(), Span::new(file, 7..8)));
+        definition
+            .parameters
+            .push(Located::new(parameter, Span::new(file, 6..13)));
+        definition.return_type = Some(Located::new(
+            Type::Primitive(PrimitiveType::Bool),
+            Span::new(file, 14..18),
+        ));
+
+        let tree = ast::Tree {
+            declarations: vec![definition.into()],
+        };
+
+        assert_eq!(
+            "Tree\n  FunctionDefinition\n    Name \"f\" 1:5-1:6\n    Parameter 1:7-1:14\n      \
+             NamePattern \"x\" 1:8-1:9\n      Type u8 1:11-1:13\n    ReturnType bool 1:15-1:19\n    Body\n",
+            ast::dump(&tree, &source_map)
+        );
+    }
+}
@@ -0,0 +1,1516 @@
+//! Contains types used to represent the structure of LifeSharp source files.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::identifier;
+use crate::location::Span;
+use crate::prelude::*;
+use crate::print::{self, Print, Printer};
+
+mod diff;
+mod dump;
+mod outline;
+
+pub use diff::{diff, Change};
+pub use dump::dump;
+pub use outline::{outline, SymbolKind, SymbolNode};
+
+/// Represents content in a source code file associated with its location.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Located<T> {
+    /// Value representing something that was parsed.
+    pub content: T,
+    /// Location in the source code file.
+    pub location: Span,
+}
+
+impl<T> Located<T> {
+    /// Associates parsed content with a location in the source file.
+    pub fn new(content: T, location: Span) -> Self {
+        Self { content, location }
+    }
+
+    /// Associates content with a [`Span::SYNTHETIC`] location, used for nodes generated by the compiler.
+    pub fn dummy(content: T) -> Self {
+        Self::new(content, Span::SYNTHETIC)
+    }
+
+    /// Returns `true` if the content was generated by the compiler rather than parsed from a source file.
+    pub fn is_synthetic(&self) -> bool {
+        self.location.is_synthetic()
+    }
+
+    /// Borrows the content, keeping its location.
+    pub fn as_ref(&self) -> Located<&T> {
+        Located::new(&self.content, self.location)
+    }
+
+    /// Transforms the content, keeping its location.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Located<U> {
+        Located::new(f(self.content), self.location)
+    }
+}
+
+impl<T: Print> Print for Located<T> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        self.content.print(printer)
+    }
+}
+
+impl<T: Print> core::fmt::Display for Located<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut printer = Printer::new(f);
+        self.print(&mut printer)?;
+        printer.finish()
+    }
+}
+
+/// An identifier in the source code file along with its location.
+pub type Id<'t> = Located<&'t identifier::Id>;
+
+/// A series of identifiers in source code used to indicate where a definition is located.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PathId<'t> {
+    /// Indicates if the path is relative or global.
+    pub global: bool,
+    /// The identifiers of the path.
+    pub identifiers: Vec<Id<'t>>,
+}
+
+impl<'t> PathId<'t> {
+    /// Creates a global path.
+    pub fn global(identifiers: Vec<Id<'t>>) -> Self {
+        Self {
+            global: true,
+            identifiers,
+        }
+    }
+
+    /// Creates a path relative to the current scope.
+    pub fn relative(identifiers: Vec<Id<'t>>) -> Self {
+        Self {
+            global: false,
+            identifiers,
+        }
+    }
+}
+
+impl Print for PathId<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        if self.global {
+            printer.write_char('\\')?;
+        }
+
+        printer.write_iter(&self.identifiers, "\\")
+    }
+}
+
+crate::print_display_impl!(PathId<'_>);
+
+impl Default for PathId<'_> {
+    /// The default path, which refers to definitions in the current scope.
+    fn default() -> Self {
+        Self {
+            global: false,
+            identifiers: Vec::default(),
+        }
+    }
+}
+
+/// An identifier that refers to a type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct TypeId<'t> {
+    /// The path to the type.
+    pub path: PathId<'t>,
+    /// The name of the type.
+    pub name: Id<'t>,
+    //pub generic_arguments: Vec<>,
+}
+
+impl<'t> TypeId<'t> {
+    /// Creates a new type with the specified path and name.
+    pub fn new(path: PathId<'t>, name: Id<'t>) -> Self {
+        Self { path, name }
+    }
+
+    /// Parses a type name of the form written by [`TypeId::print`] (e.g. `MyType`, `some\path::MyType`, or
+    /// `\::MyType`).
+    ///
+    /// The text is not tokenized, so the identifiers in the result are [`Located::dummy`] and any whitespace is an
+    /// error.
+    pub fn parse(text: &'t str) -> Result<Self, identifier::InvalidError> {
+        let (path, name) = text.rsplit_once("::").unwrap_or(("", text));
+        let (global, path) = match path.strip_prefix('\\') {
+            Some(path) => (true, path),
+            None => (false, path),
+        };
+
+        let identifiers = if path.is_empty() {
+            Vec::new()
+        } else {
+            path.split('\\')
+                .map(|segment| identifier::Id::new(segment).map(Located::dummy))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let path = if global {
+            PathId::global(identifiers)
+        } else {
+            PathId::relative(identifiers)
+        };
+
+        Ok(Self::new(path, Located::dummy(identifier::Id::new(name)?)))
+    }
+}
+
+impl Print for TypeId<'_> {
+    /// Prints the path followed by `::` and the name of the type, or only the name of the type if the path is empty and
+    /// relative (e.g. `MyType`, `some\path::MyType`, or `\::MyType`).
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        if self.path.global || !self.path.identifiers.is_empty() {
+            self.path.print(printer)?;
+            printer.write_str("::")?;
+        }
+
+        self.name.print(printer)
+    }
+}
+
+crate::print_display_impl!(TypeId<'_>);
+
+pub use crate::types::Primitive as PrimitiveType;
+
+/// Represents the name of a type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Type<'t> {
+    /// A primitive type.
+    Primitive(PrimitiveType),
+    /// A named type located with a path.
+    Named(TypeId<'t>),
+    /// A pointer to a value of the specified type (e.g. `*u8`), which can only be dereferenced in unsafe code.
+    RawPointer(Box<Located<Type<'t>>>),
+    /// Placeholder for a type that could not be parsed or resolved, for which a diagnostic was already reported.
+    ///
+    /// Later phases should accept any use of a value of this type, so that a single mistake does not cause further
+    /// diagnostics. It is printed as `<error>`, which is not valid source code.
+    Error,
+    //Array { element_type: Box<Type<'t>>, count: u32 },
+}
+
+impl Print for Type<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        match self {
+            Self::Primitive(primitive_type) => primitive_type.print(printer),
+            Self::Named(type_name) => type_name.print(printer),
+            Self::RawPointer(pointee) => {
+                printer.write_char('*')?;
+                pointee.print(printer)
+            }
+            Self::Error => printer.write_str("<error>"),
+        }
+    }
+}
+
+crate::print_display_impl!(Type<'_>);
+
+/// Represents the definition of a generic parameter in a function or type definition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct GenericParameterDefinition<'t> {
+    /// The name of the generic parameter.
+    pub name: Id<'t>,
+    /// The type of the generic parameter.
+    pub kind: GenericParameterKind<'t>,
+}
+
+/// Used to specify constraints on generic type arguments.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GenericTypeConstraint<'t> {
+    /// Requires that a generic type argument implement the specified trait.
+    Implements(TypeId<'t>),
+    //Outlives(LifetimeId<'t>),
+}
+
+impl Print for GenericTypeConstraint<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        match self {
+            Self::Implements(type_name) => type_name.print(printer),
+        }
+    }
+}
+
+crate::print_display_impl!(GenericTypeConstraint<'_>);
+
+/// Describes a generic parameter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GenericParameterKind<'t> {
+    /// Indicates that the generic parameter is a type parameter with the specified constraints.
+    Type(Vec<Located<GenericTypeConstraint<'t>>>),
+    /// Indicates that the generic parameter is a lifetime parameter.
+    Lifetime(()), //(Vec<LifetimeId<'t>>)
+}
+
+impl Print for GenericParameterDefinition<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        match &self.kind {
+            GenericParameterKind::Type(_) => printer.write_char('\'')?,
+            GenericParameterKind::Lifetime(_) => printer.write_char('~')?,
+        }
+
+        self.name.print(printer)?;
+
+        match &self.kind {
+            GenericParameterKind::Type(constraints) => {
+                if !constraints.is_empty() {
+                    printer.write_str(": ")?;
+                    printer.write_iter(constraints, ", ")?;
+                }
+            }
+            GenericParameterKind::Lifetime(()) => (),
+        }
+
+        Ok(())
+    }
+}
+
+crate::print_display_impl!(GenericParameterDefinition<'_>);
+
+/// Matches the value of a field in a record pattern (e.g. the `x = px` in `{ x = px; y = _ }`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct FieldPattern<'t> {
+    /// The name of the field.
+    pub name: Id<'t>,
+    /// The pattern applied to the value of the field.
+    pub pattern: Pattern<'t>,
+}
+
+impl<'t> FieldPattern<'t> {
+    /// Creates a pattern that matches the value of the specified field.
+    pub fn new(name: Id<'t>, pattern: Pattern<'t>) -> Self {
+        Self { name, pattern }
+    }
+}
+
+impl Print for FieldPattern<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        self.name.print(printer)?;
+        printer.write_str(" = ")?;
+        self.pattern.print(printer)
+    }
+}
+
+crate::print_display_impl!(FieldPattern<'_>);
+
+/// Matches a case of a union, applying patterns to the values contained in the case (e.g. `Some(value)`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnionCasePattern<'t> {
+    /// The name of the union case.
+    pub case: Id<'t>,
+    /// The patterns applied to each of the values contained in the case.
+    pub arguments: Vec<Pattern<'t>>,
+}
+
+impl<'t> UnionCasePattern<'t> {
+    /// Creates a pattern that matches the specified union case.
+    pub fn new(case: Id<'t>, arguments: Vec<Pattern<'t>>) -> Self {
+        Self { case, arguments }
+    }
+}
+
+impl Print for UnionCasePattern<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        self.case.print(printer)?;
+
+        if !self.arguments.is_empty() {
+            printer.write_char('(')?;
+            printer.write_iter(&self.arguments, ", ")?;
+            printer.write_char(')')?;
+        }
+
+        Ok(())
+    }
+}
+
+crate::print_display_impl!(UnionCasePattern<'_>);
+
+/// Represents a pattern.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Pattern<'t> {
+    /// Binds the matched value to the specified name.
+    Name(Id<'t>),
+    /// Ignores the value.
+    #[default]
+    Ignore,
+    /// Matches values within a range, where the bounds of the range are literals or the names of constants.
+    Range(Box<Range<'t>>),
+    /// Destructures a record, matching the values of the specified fields.
+    Record(Vec<Located<FieldPattern<'t>>>),
+    /// Matches a case of a union.
+    UnionCase(Box<UnionCasePattern<'t>>),
+}
+
+impl<'t> Pattern<'t> {
+    /// Gets the names bound by the pattern, in the order that they appear.
+    ///
+    /// The names in range patterns refer to constants, and the field names in record patterns refer to fields, so neither
+    /// introduce bindings.
+    pub fn bindings(&self) -> Vec<&Id<'t>> {
+        fn collect<'p, 't>(pattern: &'p Pattern<'t>, bindings: &mut Vec<&'p Id<'t>>) {
+            match pattern {
+                Pattern::Name(name) => bindings.push(name),
+                Pattern::Ignore | Pattern::Range(_) => (),
+                Pattern::Record(fields) => {
+                    for field in fields.iter() {
+                        collect(&field.content.pattern, bindings);
+                    }
+                }
+                Pattern::UnionCase(case) => {
+                    for argument in case.arguments.iter() {
+                        collect(argument, bindings);
+                    }
+                }
+            }
+        }
+
+        let mut bindings = Vec::new();
+        collect(self, &mut bindings);
+        bindings
+    }
+}
+
+impl Print for Pattern<'_> {
+    fn print(&self, printer: &mut Printer) -> core::fmt::Result {
+        match self {
+            Self::Name(name) => name.print(printer),
+            Self::Ignore => printer.write_char('_'),
+            Self::Range(range) => range.print(printer),
+            Self::Record(fields) => printer.group(|printer| {
+                printer.write_char('{')?;
+                printer.write_list(
+                    fields,
+                    print::ListStyle::new(";")
+                        .with_line_break(print::LineBreak::Soft)
+                        .indented(),
+                )?;
+                printer.write_char('}')
+            }),
+            Self::UnionCase(case) => case.print(printer),
+        }
+    }
+}
+
+crate::print_display_impl!(Pattern<'_>);
+
+/// A series of expressions.
+pub type Block<'t> = Vec<Located<Expression<'t>>>;
+
+/// Prints the expressions of a block that follows a keyword or `=`, delimited according to the [`print::BlockStyle`].
+fn print_block(block: &[Located<Expression<'_>>], printer: &mut Printer) -> print::Result {
+    match printer.options().block_style {
+        print::BlockStyle::Indentation => print_indented_block(block, printer),
+        print::BlockStyle::Braces => print_braced_block(true, block, printer),
+    }
+}
+
+/// Prints the expressions of a block on separate lines, with each expression indented.
+///
+/// The first expression is preceded by a soft line, allowing a block containing a single expression to be printed on the
+/// same line when it is within a group that fits.
+fn print_indented_block(block: &[Located<Expression<'_>>], printer: &mut Printer) -> print::Result {
+    let mut printer = printer.indented();
+
+    for (index, expression) in block.iter().enumerate() {
+        if index == 0 {
+            printer.soft_line()?;
+        } else {
+            printer.newline()?;
+        }
+
+        expression.print(&mut printer)?;
+    }
+
+    Ok(())
+}
+
+/// Prints the expressions of a block enclosed in curly braces.
+///
+/// A block containing a single expression is printed on one line if it fits (e.g. `{ a }`), otherwise each expression is
+/// printed on its own line. If the block is `separated` from preceding text on the same line, such as a keyword, the
+/// opening brace is placed according to the [`print::BraceStyle`].
+fn print_braced_block(
+    separated: bool,
+    block: &[Located<Expression<'_>>],
+    printer: &mut Printer,
+) -> print::Result {
+    printer.group(|printer| {
+        if separated {
+            match printer.options().brace_style {
+                print::BraceStyle::SameLine => printer.write_char(' ')?,
+                print::BraceStyle::NextLine => printer.soft_line()?,
+            }
+        }
+
+        printer.write_char('{')?;
+        print_indented_block(block, printer)?;
+        printer.soft_line()?;
+        printer.write_char('}')
+    })
+}
+
+/// Represents an `if`...`then`, `if`...`then`...`else`, or `if`...`then`...`elif`...`then`...`else` expression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct IfElseExpression<'t> {
+    /// The condition in the `if` part of the expression.
+    pub condition: Expression<'t>,
+    /// The expressions that are evaluated if the condition is true.
+    pub true_branch: Block<'t>,
+    /// The `elif` conditions and their corresponding block.
+    pub other_branches: Vec<(Expression<'t>, Block<'t>)>,
+    /// The expressions that are evaluated if no condition is met.
+    pub else_branch: Block<'t>,
+}
+
+impl Print for IfElseExpression<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        printer.group(|printer| {
+            printer.write_str("if ")?;
+            self.condition.print(printer)?;
+            printer.write_str(" then")?;
+            print_block(&self.true_branch, printer)?;
+
+            for (other_condition, other_branch) in self.other_branches.iter() {
+                printer.soft_line()?;
+                printer.write_str("elif ")?;
+                other_condition.print(printer)?;
+                printer.write_str(" then")?;
+                print_block(other_branch, printer)?;
+            }
+
+            printer.soft_line()?;
+            printer.write_str("else")?;
+            print_block(&self.else_branch, printer)
+        })
+    }
+}
+
+crate::print_display_impl!(IfElseExpression<'_>);
+
+/// Indicates how operators with the same precedence are grouped.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Associativity {
+    /// The operators are grouped from the left, `a - b - c` is evaluated as `(a - b) - c`.
+    Left,
+    /// The operators are grouped from the right, `a - b - c` would be evaluated as `a - (b - c)`.
+    Right,
+}
+
+/// Represents an operator that takes two operands.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum BinaryOperator {
+    /// The `+` operator.
+    Add,
+    /// The `-` operator.
+    Subtract,
+    /// The `*` operator.
+    Multiply,
+    /// The `/` operator.
+    Divide,
+    /// The `=` operator, which compares two values for equality.
+    Equal,
+    /// The `<` operator.
+    LessThan,
+    /// The `>` operator.
+    GreaterThan,
+    /// The `&&` operator, which only evaluates its right operand if the left operand is `true`.
+    And,
+    /// The `||` operator, which only evaluates its right operand if the left operand is `false`.
+    Or,
+    /// The `&&&` operator, which combines the bits of integers.
+    BitwiseAnd,
+    /// The `|||` operator.
+    BitwiseOr,
+    /// The `^^^` operator, which computes the exclusive or of the bits of integers.
+    BitwiseXor,
+    /// The `<<<` operator.
+    ShiftLeft,
+    /// The `>>>` operator, which performs an arithmetic shift for signed integers.
+    ShiftRight,
+}
+
+impl BinaryOperator {
+    /// Gets the precedence of the operator, operators with a higher precedence are evaluated first.
+    ///
+    /// The parser and the printer must both use this table, so that printed expressions are parsed the same way.
+    pub fn precedence(self) -> u8 {
+        match self {
+            Self::Or => 2,
+            Self::And => 3,
+            Self::Equal | Self::LessThan | Self::GreaterThan => 4,
+            Self::BitwiseOr => 5,
+            Self::BitwiseXor => 6,
+            Self::BitwiseAnd => 7,
+            Self::ShiftLeft | Self::ShiftRight => 8,
+            Self::Add | Self::Subtract => 9,
+            Self::Multiply | Self::Divide => 10,
+        }
+    }
+
+    /// Gets the associativity of the operator.
+    pub fn associativity(self) -> Associativity {
+        Associativity::Left
+    }
+}
+
+impl Print for BinaryOperator {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        printer.write_str(match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Equal => "=",
+            Self::LessThan => "<",
+            Self::GreaterThan => ">",
+            Self::And => "&&",
+            Self::Or => "||",
+            Self::BitwiseAnd => "&&&",
+            Self::BitwiseOr => "|||",
+            Self::BitwiseXor => "^^^",
+            Self::ShiftLeft => "<<<",
+            Self::ShiftRight => ">>>",
+        })
+    }
+}
+
+crate::print_display_impl!(BinaryOperator);
+
+/// Represents an operation on two operands (e.g. `a + b`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BinaryOperation<'t> {
+    /// The left operand.
+    pub left: Expression<'t>,
+    /// The operator.
+    pub operator: Located<BinaryOperator>,
+    /// The right operand.
+    pub right: Expression<'t>,
+}
+
+impl<'t> BinaryOperation<'t> {
+    /// Creates an operation with the specified operands.
+    pub fn new(
+        left: Expression<'t>,
+        operator: Located<BinaryOperator>,
+        right: Expression<'t>,
+    ) -> Self {
+        Self {
+            left,
+            operator,
+            right,
+        }
+    }
+}
+
+/// Prints an operand of an operator with the specified precedence, enclosing it in parentheses if it would otherwise be
+/// grouped differently when parsed.
+///
+/// An `associativity` of `None` indicates that the operator is not associative, so operands with the same precedence are
+/// always enclosed in parentheses.
+fn print_operand(
+    operand: &Expression<'_>,
+    precedence: u8,
+    associativity: Option<Associativity>,
+    side: Associativity,
+    printer: &mut Printer,
+) -> print::Result {
+    let parenthesize = match operand.precedence() {
+        Some(operand_precedence) if operand_precedence == precedence => associativity != Some(side),
+        Some(operand_precedence) => operand_precedence < precedence,
+        None => false,
+    };
+
+    if parenthesize {
+        printer.write_char('(')?;
+        operand.print(printer)?;
+        printer.write_char(')')
+    } else {
+        operand.print(printer)
+    }
+}
+
+impl Print for BinaryOperation<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        let operator = self.operator.content;
+        let precedence = operator.precedence();
+        let associativity = Some(operator.associativity());
+        print_operand(
+            &self.left,
+            precedence,
+            associativity,
+            Associativity::Left,
+            printer,
+        )?;
+        printer.write_char(' ')?;
+        operator.print(printer)?;
+        printer.write_char(' ')?;
+        print_operand(
+            &self.right,
+            precedence,
+            associativity,
+            Associativity::Right,
+            printer,
+        )
+    }
+}
+
+crate::print_display_impl!(BinaryOperation<'_>);
+
+/// Represents an operator that takes one operand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UnaryOperator {
+    /// The `not` operator, which negates a boolean value.
+    Not,
+}
+
+impl Print for UnaryOperator {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        printer.write_str(match self {
+            Self::Not => "not",
+        })
+    }
+}
+
+crate::print_display_impl!(UnaryOperator);
+
+/// Represents an operation on a single operand (e.g. `not a`).
+///
+/// Unary operators are applied before any binary operator, so `not a && b` is evaluated as `(not a) && b`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnaryOperation<'t> {
+    /// The operator.
+    pub operator: Located<UnaryOperator>,
+    /// The operand.
+    pub operand: Expression<'t>,
+}
+
+impl<'t> UnaryOperation<'t> {
+    /// Creates an operation with the specified operand.
+    pub fn new(operator: Located<UnaryOperator>, operand: Expression<'t>) -> Self {
+        Self { operator, operand }
+    }
+}
+
+impl Print for UnaryOperation<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        self.operator.content.print(printer)?;
+        printer.write_char(' ')?;
+        print_operand(&self.operand, u8::MAX, None, Associativity::Right, printer)
+    }
+}
+
+crate::print_display_impl!(UnaryOperation<'_>);
+
+/// Represents a range of values (e.g. `0 .. n` or `0 ..= n`), used as an expression or as a pattern.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Range<'t> {
+    /// The first value in the range.
+    pub start: Expression<'t>,
+    /// The end of the range.
+    pub end: Expression<'t>,
+    /// If `true`, the range includes the end value (`..=`), otherwise the range stops before it (`..`).
+    pub inclusive: bool,
+}
+
+impl<'t> Range<'t> {
+    /// The precedence of range expressions, which is lower than any binary operator. Ranges are not associative.
+    pub const PRECEDENCE: u8 = 1;
+
+    /// Creates a range with the specified bounds.
+    pub fn new(start: Expression<'t>, end: Expression<'t>, inclusive: bool) -> Self {
+        Self {
+            start,
+            end,
+            inclusive,
+        }
+    }
+}
+
+impl Print for Range<'_> {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        print_operand(
+            &self.start,
+            Self::PRECEDENCE,
+            None,
+            Associativity::Left,
+            printer,
+        )?;
+        printer.write_str(if self.inclusive { " ..= " } else { " .. " })?;
+        print_operand(
+            &self.end,
+            Self::PRECEDENCE,
+            None,
+            Associativity::Right,
+            printer,
+        )
+    }
+}
+
+crate::print_display_impl!(Range<'_>);
+
+/// Represents an expression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Expression<'t> {
+    /// A literal boolean value.
+    BooleanLiteral(bool),
+    /// A conditional expression.
+    IfElse(Box<IfElseExpression<'t>>),
+    /// An operation on two operands.
+    BinaryOperation(Box<BinaryOperation<'t>>),
+    /// An operation on a single operand.
+    UnaryOperation(Box<UnaryOperation<'t>>),
+    /// A range of values.
+    Range(Box<Range<'t>>),
+    /// A sequence of expressions evaluated in order, with the value of the last expression as its value (e.g.
+    /// `{ f x; x + 1 }`).
+    ///
+    /// The block introduces a new scope, so names bound inside it are not visible after the block, and can shadow names
+    /// bound outside of it.
+    Block(Block<'t>),
+    /// A block of expressions in which unsafe operations, such as dereferencing raw pointers, are allowed (e.g.
+    /// `unsafe { f x }`).
+    Unsafe(Block<'t>),
+    /// An expression explicitly enclosed in parentheses in the source code (e.g. `(a + b)`), kept so that the grouping
+    /// written by the user is preserved when the expression is printed.
+    Parenthesized(Box<Located<Expression<'t>>>),
+    //Switch,
+    //Match,
+    /// A local variable or parameter.
+    Name(Id<'t>),
+    /// Placeholder for an expression that could not be parsed or checked, for which a diagnostic was already reported.
+    ///
+    /// Later phases should treat the expression as having the [`Type::Error`] type, so that a single mistake does not
+    /// cause further diagnostics. It is printed as `<error>`, which is not valid source code.
+    Error,
+}
+
+impl Print for Expression<'_> {
+    fn print(&self, printer: &mut Printer) -> core::fmt::Result {
+        match self {
+            Self::BooleanLiteral(value) => printer.write_str(if *value { "true" } else { "false" }),
+            Self::IfElse(conditional) => conditional.print(printer),
+            Self::BinaryOperation(operation) => operation.print(printer),
+            Self::UnaryOperation(operation) => operation.print(printer),
+            Self::Range(range) => range.print(printer),
+            Self::Block(block) => print_braced_block(false, block, printer),
+            Self::Unsafe(block) => {
+                printer.write_str("unsafe")?;
+                print_braced_block(true, block, printer)
+            }
+            Self::Parenthesized(inner) => {
+                printer.write_char('(')?;
+                inner.print(printer)?;
+                printer.write_char(')')
+            }
+            Self::Name(identifier) => identifier.print(printer),
+            Self::Error => printer.write_str("<error>"),
+        }
+    }
+}
+
+impl Expression<'_> {
+    /// Gets the precedence of the expression when it is used as an operand, or `None` if the expression never needs to be
+    /// enclosed in parentheses.
+    ///
+    /// Conditional expressions have the lowest precedence, as they extend as far to the right as possible.
+    pub fn precedence(&self) -> Option<u8> {
+        match self {
+            Self::BinaryOperation(operation) => Some(operation.operator.content.precedence()),
+            Self::Range(_) => Some(Range::PRECEDENCE),
+            Self::IfElse(_) => Some(0),
+            Self::BooleanLiteral(_)
+            | Self::UnaryOperation(_)
+            | Self::Block(_)
+            | Self::Unsafe(_)
+            | Self::Parenthesized(_)
+            | Self::Name(_)
+            | Self::Error => None,
+        }
+    }
+}
+
+impl<'t> Expression<'t> {
+    /// Gets the expressions directly inside of this expression, in the order that they are written in.
+    ///
+    /// Passes that visit every expression in a tree should push the children onto a stack instead of recursing, so that
+    /// deeply nested expressions cannot overflow the call stack.
+    pub fn children(&self) -> Vec<&Expression<'t>> {
+        match self {
+            Self::BooleanLiteral(_) | Self::Name(_) | Self::Error => Vec::new(),
+            Self::IfElse(conditional) => {
+                let mut children = vec![&conditional.condition];
+                children.extend(conditional.true_branch.iter().map(|nested| &nested.content));
+                for (condition, branch) in conditional.other_branches.iter() {
+                    children.push(condition);
+                    children.extend(branch.iter().map(|nested| &nested.content));
+                }
+                children.extend(conditional.else_branch.iter().map(|nested| &nested.content));
+                children
+            }
+            Self::BinaryOperation(operation) => vec![&operation.left, &operation.right],
+            Self::UnaryOperation(operation) => vec![&operation.operand],
+            Self::Range(range) => vec![&range.start, &range.end],
+            Self::Block(block) | Self::Unsafe(block) => {
+                block.iter().map(|nested| &nested.content).collect()
+            }
+            Self::Parenthesized(inner) => vec![&inner.content],
+        }
+    }
+
+    /// Gets mutable references to the expressions directly inside of this expression, in the same order as
+    /// [`Expression::children`].
+    pub fn children_mut(&mut self) -> Vec<&mut Expression<'t>> {
+        match self {
+            Self::BooleanLiteral(_) | Self::Name(_) | Self::Error => Vec::new(),
+            Self::IfElse(conditional) => {
+                let mut children = vec![&mut conditional.condition];
+                children.extend(
+                    conditional
+                        .true_branch
+                        .iter_mut()
+                        .map(|nested| &mut nested.content),
+                );
+                for (condition, branch) in conditional.other_branches.iter_mut() {
+                    children.push(condition);
+                    children.extend(branch.iter_mut().map(|nested| &mut nested.content));
+                }
+                children.extend(
+                    conditional
+                        .else_branch
+                        .iter_mut()
+                        .map(|nested| &mut nested.content),
+                );
+                children
+            }
+            Self::BinaryOperation(operation) => vec![&mut operation.left, &mut operation.right],
+            Self::UnaryOperation(operation) => vec![&mut operation.operand],
+            Self::Range(range) => vec![&mut range.start, &mut range.end],
+            Self::Block(block) | Self::Unsafe(block) => {
+                block.iter_mut().map(|nested| &mut nested.content).collect()
+            }
+            Self::Parenthesized(inner) => vec![&mut inner.content],
+        }
+    }
+}
+
+crate::print_display_impl!(Expression<'_>);
+
+/// Represents a parameter in a function definition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Parameter<'t> {
+    /// Pattern applied to the argument.
+    pub pattern: Pattern<'t>,
+    // TODO: Might be duplicated if Name pattern allows a type in it. Could remove explicit type here to allow type inference for parameters.
+    /// The type of the parameter.
+    pub argument_type: Located<Type<'t>>,
+}
+
+impl<'t> Parameter<'t> {
+    /// Creates a parameter with the specified type.
+    pub fn new(argument_type: Located<Type<'t>>) -> Self {
+        Self {
+            pattern: Pattern::Ignore,
+            argument_type,
+        }
+    }
+}
+
+impl Print for Parameter<'_> {
+    fn print(&self, printer: &mut Printer) -> core::fmt::Result {
+        printer.write_char('(')?;
+        self.pattern.print(printer)?;
+        printer.write_str(": ")?;
+        self.argument_type.print(printer)?;
+        printer.write_char(')')
+    }
+}
+
+crate::print_display_impl!(Parameter<'_>);
+
+/// Represents a function definition.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct FunctionDefinition<'t> {
+    /// The name of the function.
+    pub name: Id<'t>,
+    /// The generic parameters of the function.
+    pub generic_parameters: Vec<Located<GenericParameterDefinition<'t>>>,
+    /// The parameters of the function.
+    pub parameters: Vec<Located<Parameter<'t>>>,
+    /// The return type of the function.
+    pub return_type: Option<Located<Type<'t>>>,
+    /// The expressions that make up the function body.
+    pub body: Block<'t>,
+    /// If `true`, the function can only be called in unsafe code, and unsafe operations are allowed in its body.
+    pub is_unsafe: bool,
+    /// The location of the entire definition, starting at the `unsafe` or `def` keyword and ending after the body.
+    pub span: Span,
+}
+
+impl<'t> FunctionDefinition<'t> {
+    /// Creates a function definition with the specified name.
+    pub fn new(name: Id<'t>) -> Self {
+        Self {
+            name,
+            generic_parameters: Vec::default(),
+            parameters: Vec::default(),
+            return_type: None,
+            body: Vec::default(),
+            is_unsafe: false,
+            span: Span::SYNTHETIC,
+        }
+    }
+}
+
+impl Print for FunctionDefinition<'_> {
+    fn print(&self, printer: &mut Printer) -> core::fmt::Result {
+        printer.group(|printer| {
+            if self.is_unsafe {
+                printer.write_str("unsafe ")?;
+            }
+
+            printer.write_str("def ")?;
+            self.name.print(printer)?;
+
+            if !self.generic_parameters.is_empty() {
+                printer.write_char('<')?;
+                printer.write_iter(&self.generic_parameters, ", ")?;
+                printer.write_char('>')?;
+            }
+
+            printer.write_list(
+                &self.parameters,
+                print::ListStyle::new("")
+                    .with_line_break(print::LineBreak::Soft)
+                    .without_break_after_last()
+                    .indented()
+                    .with_empty(" ()"),
+            )
+        })?;
+
+        if let Some(return_type) = &self.return_type {
+            printer.write_char(' ')?;
+            return_type.print(printer)?;
+        }
+
+        printer.write_str(" =")?;
+        print_block(&self.body, printer)
+    }
+}
+
+crate::print_display_impl!(FunctionDefinition<'_>);
+
+/// Represents a top-level declaration defined in a source code file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TopDeclaration<'t> {
+    /// A function definition defined at the top level.
+    FunctionDefinition(Box<FunctionDefinition<'t>>),
+}
+
+impl<'t> TopDeclaration<'t> {
+    /// Gets the name of the declaration.
+    pub fn name(&self) -> &Id<'t> {
+        match self {
+            Self::FunctionDefinition(function_definition) => &function_definition.name,
+        }
+    }
+}
+
+impl<'t> From<FunctionDefinition<'t>> for TopDeclaration<'t> {
+    fn from(function_definition: FunctionDefinition<'t>) -> Self {
+        Self::FunctionDefinition(Box::new(function_definition))
+    }
+}
+
+impl Print for TopDeclaration<'_> {
+    fn print(&self, printer: &mut Printer) -> core::fmt::Result {
+        match self {
+            Self::FunctionDefinition(function_definition) => function_definition.print(printer),
+        }
+    }
+}
+
+crate::print_display_impl!(TopDeclaration<'_>);
+
+/// Represents the content of a single source file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Tree<'t> {
+    //header: like how files in F# can start with module or namespace?
+    /// The top-level declarations declared in the source file.
+    pub declarations: Vec<TopDeclaration<'t>>,
+}
+
+impl Print for Tree<'_> {
+    fn print(&self, printer: &mut Printer) -> core::fmt::Result {
+        for (index, declaration) in self.declarations.iter().enumerate() {
+            if index > 0 {
+                printer.newline()?;
+                printer.newline()?;
+            }
+
+            declaration.print(printer)?;
+        }
+
+        if printer.options().trailing_newline && !self.declarations.is_empty() {
+            printer.newline()?;
+        }
+
+        Ok(())
+    }
+}
+
+crate::print_display_impl!(Tree<'_>);
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{
+        BinaryOperation, BinaryOperator, Expression, FieldPattern, FunctionDefinition,
+        IfElseExpression, Located, Parameter, PathId, Pattern, PrimitiveType, Range, Type, TypeId,
+        UnaryOperation, UnaryOperator, UnionCasePattern,
+    };
+    use crate::identifier::Id;
+    use crate::location::{FileId, Span};
+    use crate::print;
+
+    fn name(name: &str) -> Expression<'_> {
+        Expression::Name(Located::dummy(Id::new(name).unwrap()))
+    }
+
+    fn if_else<'t>(
+        condition: &'t str,
+        true_value: &'t str,
+        false_value: &'t str,
+    ) -> Expression<'t> {
+        Expression::IfElse(Box::new(IfElseExpression {
+            condition: name(condition),
+            true_branch: vec![Located::dummy(name(true_value))],
+            other_branches: Vec::new(),
+            else_branch: vec![Located::dummy(name(false_value))],
+        }))
+    }
+
+    #[test]
+    fn children_are_in_written_order() {
+        let mut expression = if_else("a", "b", "c");
+        assert_eq!(
+            vec!["a", "b", "c"],
+            expression
+                .children()
+                .into_iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+
+        *expression.children_mut()[1] = Expression::BooleanLiteral(true);
+        assert_eq!(Expression::BooleanLiteral(true), *expression.children()[1]);
+        assert!(name("d").children().is_empty());
+    }
+
+    #[test]
+    fn unsafe_code_is_printed() {
+        let mut definition = FunctionDefinition::new(Located::dummy(Id::new("read").unwrap()));
+        definition.is_unsafe = true;
+        definition
+            .parameters
+            .push(Located::dummy(Parameter::new(Located::dummy(
+                Type::RawPointer(Box::new(Located::dummy(Type::Primitive(PrimitiveType::U8)))),
+            ))));
+        definition
+            .body
+            .push(Located::dummy(Expression::Unsafe(vec![Located::dummy(
+                name("a"),
+            )])));
+
+        assert_eq!(
+            "unsafe def read (_: *u8) =\n    unsafe { a }",
+            definition.to_string()
+        );
+    }
+
+    #[test]
+    fn blocks_are_enclosed_in_braces() {
+        let short = operation(
+            Expression::Block(vec![Located::dummy(name("a"))]),
+            BinaryOperator::Add,
+            name("b"),
+        );
+        assert_eq!("{ a } + b", short.to_string());
+
+        let long = Expression::Unsafe(vec![Located::dummy(name("a")), Located::dummy(name("b"))]);
+        assert_eq!("unsafe {\n    a\n    b\n}", long.to_string());
+
+        let options = print::Options {
+            brace_style: print::BraceStyle::NextLine,
+            ..print::Options::default()
+        };
+        assert_eq!(
+            "unsafe\n{\n    a\n    b\n}",
+            print::to_string_with_options(&long, options)
+        );
+    }
+
+    #[test]
+    fn blocks_are_normalized_to_braces() {
+        let options = print::Options {
+            block_style: print::BlockStyle::Braces,
+            ..print::Options::default()
+        };
+
+        assert_eq!(
+            "if c then { a } else { b }",
+            print::to_string_with_options(&if_else("c", "a", "b"), options.clone())
+        );
+
+        let mut definition = FunctionDefinition::new(Located::dummy(Id::new("f").unwrap()));
+        definition.body.push(Located::dummy(name("a")));
+        definition.body.push(Located::dummy(name("b")));
+        assert_eq!(
+            "def f () = {\n    a\n    b\n}",
+            print::to_string_with_options(&definition, options)
+        );
+    }
+
+    #[test]
+    fn return_type_is_included_when_checking_if_parameters_fit() {
+        let parameter = |name: &'static str| {
+            Located::dummy(Parameter {
+                pattern: Pattern::Name(Located::dummy(Id::new(name).unwrap())),
+                argument_type: Located::dummy(Type::Primitive(PrimitiveType::U32)),
+            })
+        };
+
+        let mut definition = FunctionDefinition::new(Located::dummy(Id::new("function").unwrap()));
+        definition.parameters.push(parameter("first"));
+        definition.parameters.push(parameter("second"));
+        definition.return_type = Some(Located::dummy(Type::Primitive(PrimitiveType::U64)));
+        definition
+            .body
+            .push(Located::dummy(Expression::BooleanLiteral(true)));
+
+        let printed = print::to_string_with_options(
+            &definition,
+            print::Options {
+                max_width: 40,
+                ..print::Options::default()
+            },
+        );
+        assert_eq!(
+            "def function\n    (first: u32)\n    (second: u32) u64 =\n    true",
+            printed
+        );
+        assert!(printed.lines().all(|line| line.len() <= 40));
+    }
+
+    #[test]
+    fn short_if_else_is_printed_on_one_line() {
+        assert_eq!("if c then a else b", if_else("c", "a", "b").to_string());
+    }
+
+    #[test]
+    fn long_if_else_is_broken_across_lines() {
+        let long_name = "a".repeat(100);
+        assert_eq!(
+            format!("if c then\n    {}\nelse\n    b", long_name),
+            if_else("c", &long_name, "b").to_string()
+        );
+    }
+
+    fn operation<'t>(
+        left: Expression<'t>,
+        operator: BinaryOperator,
+        right: Expression<'t>,
+    ) -> Expression<'t> {
+        Expression::BinaryOperation(Box::new(BinaryOperation::new(
+            left,
+            Located::dummy(operator),
+            right,
+        )))
+    }
+
+    #[test]
+    fn left_associative_operation_is_not_parenthesized() {
+        let expression = operation(
+            operation(name("a"), BinaryOperator::Subtract, name("b")),
+            BinaryOperator::Subtract,
+            name("c"),
+        );
+
+        assert_eq!("a - b - c", expression.to_string());
+    }
+
+    #[test]
+    fn right_nested_operation_with_same_precedence_is_parenthesized() {
+        let expression = operation(
+            name("a"),
+            BinaryOperator::Subtract,
+            operation(name("b"), BinaryOperator::Add, name("c")),
+        );
+
+        assert_eq!("a - (b + c)", expression.to_string());
+    }
+
+    #[test]
+    fn operation_with_lower_precedence_is_parenthesized() {
+        let expression = operation(
+            operation(name("a"), BinaryOperator::Add, name("b")),
+            BinaryOperator::Multiply,
+            name("c"),
+        );
+
+        assert_eq!("(a + b) * c", expression.to_string());
+    }
+
+    #[test]
+    fn operation_with_higher_precedence_is_not_parenthesized() {
+        let expression = operation(
+            name("a"),
+            BinaryOperator::Add,
+            operation(name("b"), BinaryOperator::Multiply, name("c")),
+        );
+
+        assert_eq!("a + b * c", expression.to_string());
+    }
+
+    #[test]
+    fn conditional_operand_is_parenthesized() {
+        let expression = operation(if_else("c", "a", "b"), BinaryOperator::Add, name("d"));
+        assert_eq!("(if c then a else b) + d", expression.to_string());
+    }
+
+    #[test]
+    fn logical_operators_have_lower_precedence_than_comparisons() {
+        let expression = operation(
+            operation(name("a"), BinaryOperator::LessThan, name("b")),
+            BinaryOperator::Or,
+            operation(
+                operation(name("c"), BinaryOperator::BitwiseAnd, name("d")),
+                BinaryOperator::And,
+                name("e"),
+            ),
+        );
+        assert_eq!("a < b || c &&& d && e", expression.to_string());
+    }
+
+    #[test]
+    fn unary_operand_is_parenthesized_unless_simple() {
+        let not = |operand| {
+            Expression::UnaryOperation(Box::new(UnaryOperation::new(
+                Located::dummy(UnaryOperator::Not),
+                operand,
+            )))
+        };
+
+        let expression = operation(
+            not(operation(name("a"), BinaryOperator::Equal, name("b"))),
+            BinaryOperator::And,
+            not(not(name("c"))),
+        );
+        assert_eq!("not (a = b) && not not c", expression.to_string());
+    }
+
+    #[test]
+    fn type_names_are_parsed_again() {
+        for (global, segments, printed) in [
+            (false, &[][..], "T"),
+            (false, &["a"][..], "a::T"),
+            (false, &["a", "b"][..], "a\\b::T"),
+            (true, &[][..], "\\::T"),
+            (true, &["a", "b"][..], "\\a\\b::T"),
+        ] {
+            let identifiers = segments
+                .iter()
+                .map(|segment| Located::dummy(Id::new(segment).unwrap()))
+                .collect();
+            let path = if global {
+                PathId::global(identifiers)
+            } else {
+                PathId::relative(identifiers)
+            };
+
+            let type_id = TypeId::new(path, Located::dummy(Id::new("T").unwrap()));
+            assert_eq!(printed, type_id.to_string());
+            assert_eq!(type_id, TypeId::parse(printed).unwrap());
+        }
+
+        assert!(TypeId::parse("a\\\\b::T").is_err());
+        assert!(TypeId::parse("a::").is_err());
+    }
+
+    #[test]
+    fn error_operand_is_not_parenthesized() {
+        let expression = operation(Expression::Error, BinaryOperator::Multiply, name("a"));
+        assert_eq!("<error> * a", expression.to_string());
+    }
+
+    #[test]
+    fn parenthesized_operand_is_printed_once() {
+        let expression = operation(
+            name("a"),
+            BinaryOperator::Multiply,
+            Expression::Parenthesized(Box::new(Located::dummy(operation(
+                name("b"),
+                BinaryOperator::Add,
+                name("c"),
+            )))),
+        );
+
+        assert_eq!("a * (b + c)", expression.to_string());
+    }
+
+    #[test]
+    fn redundant_parentheses_are_preserved() {
+        let expression = operation(
+            Expression::Parenthesized(Box::new(Located::dummy(operation(
+                name("a"),
+                BinaryOperator::Multiply,
+                name("b"),
+            )))),
+            BinaryOperator::Add,
+            name("c"),
+        );
+
+        assert_eq!("(a * b) + c", expression.to_string());
+    }
+
+    fn range<'t>(start: Expression<'t>, end: Expression<'t>, inclusive: bool) -> Expression<'t> {
+        Expression::Range(Box::new(Range::new(start, end, inclusive)))
+    }
+
+    #[test]
+    fn range_bounds_with_operators_are_not_parenthesized() {
+        let expression = range(
+            name("a"),
+            operation(name("n"), BinaryOperator::Subtract, name("b")),
+            false,
+        );
+
+        assert_eq!("a .. n - b", expression.to_string());
+    }
+
+    #[test]
+    fn range_operands_are_parenthesized() {
+        let expression = operation(
+            range(name("a"), name("b"), true),
+            BinaryOperator::Equal,
+            name("c"),
+        );
+
+        assert_eq!("(a ..= b) = c", expression.to_string());
+        assert_eq!(
+            "(a .. b) .. c",
+            range(range(name("a"), name("b"), false), name("c"), false).to_string()
+        );
+    }
+
+    #[test]
+    fn range_pattern_is_printed() {
+        let pattern = Pattern::Range(Box::new(Range::new(name("low"), name("high"), true)));
+        assert_eq!("low ..= high", pattern.to_string());
+    }
+
+    fn name_pattern(name: &str) -> Pattern<'_> {
+        Pattern::Name(Located::dummy(Id::new(name).unwrap()))
+    }
+
+    #[test]
+    fn record_pattern_is_printed_on_one_line() {
+        let pattern = Pattern::Record(vec![
+            Located::dummy(FieldPattern::new(
+                Located::dummy(Id::new("x").unwrap()),
+                name_pattern("px"),
+            )),
+            Located::dummy(FieldPattern::new(
+                Located::dummy(Id::new("y").unwrap()),
+                Pattern::Ignore,
+            )),
+        ]);
+
+        assert_eq!("{ x = px; y = _ }", pattern.to_string());
+    }
+
+    #[test]
+    fn nested_union_case_pattern_is_printed() {
+        let inner = Pattern::UnionCase(Box::new(UnionCasePattern::new(
+            Located::dummy(Id::new("Some").unwrap()),
+            vec![name_pattern("value")],
+        )));
+        let pattern = Pattern::UnionCase(Box::new(UnionCasePattern::new(
+            Located::dummy(Id::new("Pair").unwrap()),
+            vec![
+                inner,
+                Pattern::UnionCase(Box::new(UnionCasePattern::new(
+                    Located::dummy(Id::new("None").unwrap()),
+                    Vec::new(),
+                ))),
+            ],
+        )));
+
+        assert_eq!("Pair(Some(value), None)", pattern.to_string());
+    }
+
+    #[test]
+    fn mapped_content_keeps_location() {
+        let location = Span::new(FileId::default(), 4..7);
+        let located = Located::new(2, location).map(|value| value * 2);
+        assert_eq!(4, located.content);
+        assert_eq!(location, located.location);
+        assert!(!located.is_synthetic());
+        assert!(Located::dummy(()).as_ref().is_synthetic());
+    }
+
+    #[test]
+    fn nested_pattern_bindings_are_collected() {
+        let pattern = Pattern::UnionCase(Box::new(UnionCasePattern::new(
+            Located::dummy(Id::new("Some").unwrap()),
+            vec![Pattern::Record(vec![
+                Located::dummy(FieldPattern::new(
+                    Located::dummy(Id::new("x").unwrap()),
+                    name_pattern("px"),
+                )),
+                Located::dummy(FieldPattern::new(
+                    Located::dummy(Id::new("y").unwrap()),
+                    Pattern::Ignore,
+                )),
+                Located::dummy(FieldPattern::new(
+                    Located::dummy(Id::new("z").unwrap()),
+                    name_pattern("pz"),
+                )),
+            ])],
+        )));
+
+        let names = pattern
+            .bindings()
+            .into_iter()
+            .map(|name| name.content.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["px", "pz"], names);
+    }
+}
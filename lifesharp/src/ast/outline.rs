@@ -0,0 +1,129 @@
+//! Lists the symbols declared in a source file, used by tools such as editors to show an outline of the file.
+
+use crate::ast::{FunctionDefinition, TopDeclaration, Tree};
+use crate::identifier::Identifier;
+use crate::location::{self, Span};
+use crate::prelude::*;
+
+/// Describes what a symbol in an outline refers to.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SymbolKind {
+    /// A function definition.
+    Function,
+}
+
+/// A symbol declared in a source file, along with the symbols nested within it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SymbolNode {
+    /// The name of the symbol.
+    pub name: Identifier,
+    /// Indicates what the symbol refers to.
+    pub kind: SymbolKind,
+    /// The location of the entire declaration.
+    pub span: Span,
+    /// The location of the name of the symbol.
+    pub name_span: Span,
+    /// The symbols declared within this symbol.
+    pub children: Vec<SymbolNode>,
+}
+
+/// Gets the location of an entire function definition.
+///
+/// The parts of the definition are merged into its span, so that definitions created without a span still cover their
+/// name, generic parameters, parameters, return type, and body.
+fn definition_span(definition: &FunctionDefinition<'_>) -> Span {
+    definition
+        .generic_parameters
+        .iter()
+        .map(|parameter| parameter.location)
+        .chain(
+            definition
+                .parameters
+                .iter()
+                .map(|parameter| parameter.location),
+        )
+        .chain(
+            definition
+                .return_type
+                .iter()
+                .map(|return_type| return_type.location),
+        )
+        .chain(definition.body.iter().map(|expression| expression.location))
+        .fold(
+            location::span_merge(definition.span, definition.name.location),
+            location::span_merge,
+        )
+}
+
+/// Gets the symbols declared at the top level of a source file, in the order that they are declared.
+pub fn outline(tree: &Tree<'_>) -> Vec<SymbolNode> {
+    tree.declarations
+        .iter()
+        .map(|declaration| match declaration {
+            TopDeclaration::FunctionDefinition(definition) => SymbolNode {
+                name: definition.name.content.to_identifier(),
+                kind: SymbolKind::Function,
+                span: definition_span(definition),
+                name_span: definition.name.location,
+                children: Vec::new(),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{self, Expression, FunctionDefinition, Located, SymbolKind, Type};
+    use crate::identifier::Id;
+    use crate::location::{FileId, Span};
+    use crate::types::Primitive;
+
+    #[test]
+    fn function_span_covers_body() {
+        let file = FileId::default();
+        let mut definition =
+            FunctionDefinition::new(Located::new(Id::new("f").unwrap(), Span::new(file, 4..5)));
+        definition.body.push(Located::new(
+            Expression::BooleanLiteral(true),
+            Span::new(file, 16..20),
+        ));
+
+        let tree = ast::Tree {
+            declarations: vec![definition.into()],
+        };
+
+        let symbols = ast::outline(&tree);
+        assert_eq!(1, symbols.len());
+        assert_eq!("f", symbols[0].name.as_str());
+        assert_eq!(SymbolKind::Function, symbols[0].kind);
+        assert_eq!(Span::new(file, 4..20), symbols[0].span);
+        assert_eq!(Span::new(file, 4..5), symbols[0].name_span);
+    }
+
+    #[test]
+    fn function_span_covers_declaration() {
+        let file = FileId::default();
+        let mut definition =
+            FunctionDefinition::new(Located::new(Id::new("f").unwrap(), Span::new(file, 11..12)));
+        definition.return_type = Some(Located::new(
+            Type::Primitive(Primitive::Bool),
+            Span::new(file, 18..22),
+        ));
+
+        let tree = ast::Tree {
+            declarations: vec![definition.clone().into()],
+        };
+
+        assert_eq!(Span::new(file, 11..22), ast::outline(&tree)[0].span);
+
+        definition.is_unsafe = true;
+        definition.span = Span::new(file, 0..22);
+        let tree = ast::Tree {
+            declarations: vec![definition.into()],
+        };
+
+        assert_eq!(Span::new(file, 0..22), ast::outline(&tree)[0].span);
+    }
+}
@@ -0,0 +1,403 @@
+//! Lossless concrete syntax trees, which retain every token and piece of trivia in a source file.
+//!
+//! Trees are stored in two layers. The immutable *green* layer records the kind and text of each node and token, along
+//! with their widths, and can be shared between versions of a tree. The *red* layer wraps green nodes with their
+//! absolute offsets and parents, and is created on demand while traversing the tree.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::lexer::TokenKind;
+use crate::location::{FileId, Offset, Span};
+use crate::prelude::*;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+/// The kind of a node in a concrete syntax tree.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum NodeKind {
+    /// The root node, containing the contents of a source file.
+    Tree,
+    /// A function definition, such as `def f (x: u32) = x`.
+    FunctionDefinition,
+    /// The list of parameters of a function definition.
+    ParameterList,
+    /// A parameter of a function definition.
+    Parameter,
+    /// A type annotation.
+    Type,
+    /// A pattern.
+    Pattern,
+    /// A sequence of expressions.
+    Block,
+    /// An expression.
+    Expression,
+    /// Contains tokens that could not be parsed.
+    Error,
+}
+
+/// The kind of a leaf in a concrete syntax tree.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum LeafKind {
+    /// A token produced by the lexer.
+    Token(TokenKind),
+    /// Spaces, tabs, and line breaks between tokens.
+    Whitespace,
+    /// A comment.
+    Comment,
+}
+
+impl LeafKind {
+    /// Returns `true` if the leaf is whitespace or a comment, which do not affect the meaning of the source code.
+    pub fn is_trivia(self) -> bool {
+        matches!(self, Self::Whitespace | Self::Comment)
+    }
+}
+
+/// A leaf in the green layer, containing the text of a token or trivia.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GreenToken {
+    kind: LeafKind,
+    text: Arc<str>,
+}
+
+impl GreenToken {
+    /// Creates a leaf with the specified text.
+    pub fn new(kind: LeafKind, text: &str) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+
+    /// Gets the kind of the leaf.
+    pub fn kind(&self) -> LeafKind {
+        self.kind
+    }
+
+    /// Gets the text of the leaf.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Gets the length of the text of the leaf, in bytes.
+    pub fn width(&self) -> usize {
+        self.text.len()
+    }
+}
+
+/// A child of a node in the green layer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GreenElement {
+    /// A nested node.
+    Node(GreenNode),
+    /// A token or trivia.
+    Token(GreenToken),
+}
+
+impl GreenElement {
+    /// Gets the length of the text covered by the element, in bytes.
+    pub fn width(&self) -> usize {
+        match self {
+            Self::Node(node) => node.width(),
+            Self::Token(token) => token.width(),
+        }
+    }
+}
+
+/// A node in the green layer, which is immutable and does not know its position in the source file.
+///
+/// Cloning a green node is cheap, since its children are shared.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GreenNode {
+    kind: NodeKind,
+    width: usize,
+    children: Arc<[GreenElement]>,
+}
+
+impl GreenNode {
+    /// Creates a node containing the specified children.
+    pub fn new(kind: NodeKind, children: Vec<GreenElement>) -> Self {
+        Self {
+            kind,
+            width: children.iter().map(GreenElement::width).sum(),
+            children: children.into(),
+        }
+    }
+
+    /// Gets the kind of the node.
+    pub fn kind(&self) -> NodeKind {
+        self.kind
+    }
+
+    /// Gets the length of the text covered by the node, in bytes.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the children of the node.
+    pub fn children(&self) -> &[GreenElement] {
+        &self.children
+    }
+
+    fn write_text(&self, text: &mut String) {
+        for child in self.children.iter() {
+            match child {
+                GreenElement::Node(node) => node.write_text(text),
+                GreenElement::Token(token) => text.push_str(token.text()),
+            }
+        }
+    }
+}
+
+/// Builds a green tree from the top down, as a parser would produce it.
+#[derive(Debug, Default)]
+pub struct Builder {
+    parents: Vec<(NodeKind, usize)>,
+    children: Vec<GreenElement>,
+}
+
+impl Builder {
+    /// Starts a new node, which will contain every element added until the matching call to [`Builder::finish_node`].
+    pub fn start_node(&mut self, kind: NodeKind) {
+        self.parents.push((kind, self.children.len()));
+    }
+
+    /// Adds a token or trivia to the current node.
+    pub fn token(&mut self, kind: LeafKind, text: &str) {
+        self.children
+            .push(GreenElement::Token(GreenToken::new(kind, text)));
+    }
+
+    /// Finishes the current node.
+    ///
+    /// # Panics
+    /// Panics if there is no node to finish.
+    pub fn finish_node(&mut self) {
+        let (kind, first_child) = self.parents.pop().expect("no node was started");
+        let children = self.children.split_off(first_child);
+        self.children
+            .push(GreenElement::Node(GreenNode::new(kind, children)));
+    }
+
+    /// Gets the root node of the tree.
+    ///
+    /// # Panics
+    /// Panics if a node was not finished, or if the tree does not have exactly one root node.
+    pub fn finish(mut self) -> GreenNode {
+        assert!(self.parents.is_empty(), "a node was not finished");
+        match (self.children.pop(), self.children.is_empty()) {
+            (Some(GreenElement::Node(root)), true) => root,
+            _ => panic!("tree must have exactly one root node"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NodeData {
+    green: GreenNode,
+    file: FileId,
+    offset: Offset,
+    parent: Option<SyntaxNode>,
+}
+
+/// A node in the red layer, which knows its position in the source file and its parent.
+#[derive(Clone, Debug)]
+pub struct SyntaxNode(Rc<NodeData>);
+
+/// A token or trivia in the red layer.
+#[derive(Clone, Debug)]
+pub struct SyntaxToken {
+    green: GreenToken,
+    offset: Offset,
+    parent: SyntaxNode,
+}
+
+/// A child of a [`SyntaxNode`].
+#[derive(Clone, Debug)]
+pub enum SyntaxElement {
+    /// A nested node.
+    Node(SyntaxNode),
+    /// A token or trivia.
+    Token(SyntaxToken),
+}
+
+impl SyntaxNode {
+    /// Creates the root node of a tree for the source file with the specified identifier.
+    pub fn new_root(green: GreenNode, file: FileId) -> Self {
+        Self(Rc::new(NodeData {
+            green,
+            file,
+            offset: 0,
+            parent: None,
+        }))
+    }
+
+    /// Gets the underlying green node.
+    pub fn green(&self) -> &GreenNode {
+        &self.0.green
+    }
+
+    /// Gets the kind of the node.
+    pub fn kind(&self) -> NodeKind {
+        self.0.green.kind()
+    }
+
+    /// Gets the source file that the node is in.
+    pub fn file(&self) -> FileId {
+        self.0.file
+    }
+
+    /// Gets the span of the source file covered by the node.
+    pub fn text_range(&self) -> Span {
+        Span::new(
+            self.0.file,
+            self.0.offset..self.0.offset + self.0.green.width(),
+        )
+    }
+
+    /// Gets the node containing this node, or `None` if this is the root.
+    pub fn parent(&self) -> Option<&SyntaxNode> {
+        self.0.parent.as_ref()
+    }
+
+    /// Gets the children of the node, including trivia.
+    pub fn children(&self) -> impl Iterator<Item = SyntaxElement> + '_ {
+        let mut offset = self.0.offset;
+        self.0.green.children().iter().map(move |child| {
+            let start = offset;
+            offset += child.width();
+            match child {
+                GreenElement::Node(green) => SyntaxElement::Node(Self(Rc::new(NodeData {
+                    green: green.clone(),
+                    file: self.0.file,
+                    offset: start,
+                    parent: Some(self.clone()),
+                }))),
+                GreenElement::Token(green) => SyntaxElement::Token(SyntaxToken {
+                    green: green.clone(),
+                    offset: start,
+                    parent: self.clone(),
+                }),
+            }
+        })
+    }
+
+    /// Gets the child nodes of this node, skipping tokens and trivia.
+    pub fn child_nodes(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.children().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Gets the innermost token that contains the specified offset.
+    pub fn token_at_offset(&self, offset: Offset) -> Option<SyntaxToken> {
+        for child in self.children() {
+            match child {
+                SyntaxElement::Node(node) if node.text_range().contains(offset) => {
+                    return node.token_at_offset(offset)
+                }
+                SyntaxElement::Token(token) if token.text_range().contains(offset) => {
+                    return Some(token)
+                }
+                _ => (),
+            }
+        }
+
+        None
+    }
+
+    /// Gets the source code covered by the node, exactly as it was written.
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity(self.0.green.width());
+        self.0.green.write_text(&mut text);
+        text
+    }
+}
+
+impl SyntaxToken {
+    /// Gets the kind of the token.
+    pub fn kind(&self) -> LeafKind {
+        self.green.kind()
+    }
+
+    /// Gets the text of the token.
+    pub fn text(&self) -> &str {
+        self.green.text()
+    }
+
+    /// Gets the span of the source file covered by the token.
+    pub fn text_range(&self) -> Span {
+        Span::new(
+            self.parent.file(),
+            self.offset..self.offset + self.green.width(),
+        )
+    }
+
+    /// Gets the node containing the token.
+    pub fn parent(&self) -> &SyntaxNode {
+        &self.parent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cst::{Builder, LeafKind, NodeKind, SyntaxElement, SyntaxNode};
+    use crate::lexer::TokenKind;
+    use crate::location::{FileId, Span};
+
+    /// Builds the tree for `(a + b) // sum`, where `a` and `b` are written as periods since identifiers are not yet
+    /// tokenized.
+    fn example() -> SyntaxNode {
+        let mut builder = Builder::default();
+        builder.start_node(NodeKind::Tree);
+        builder.start_node(NodeKind::Expression);
+        builder.token(LeafKind::Token(TokenKind::OpenParenthesis), "(");
+        builder.token(LeafKind::Token(TokenKind::Period), ".");
+        builder.token(LeafKind::Whitespace, " ");
+        builder.token(LeafKind::Token(TokenKind::PlusSign), "+");
+        builder.token(LeafKind::Whitespace, " ");
+        builder.token(LeafKind::Token(TokenKind::Period), ".");
+        builder.token(LeafKind::Token(TokenKind::CloseParenthesis), ")");
+        builder.finish_node();
+        builder.token(LeafKind::Whitespace, " ");
+        builder.token(LeafKind::Comment, "// sum");
+        builder.finish_node();
+        SyntaxNode::new_root(builder.finish(), FileId::new(1))
+    }
+
+    #[test]
+    fn text_is_preserved() {
+        let tree = example();
+        assert_eq!("(. + .) // sum", tree.text());
+        assert_eq!(Span::new(FileId::new(1), 0..14), tree.text_range());
+    }
+
+    #[test]
+    fn children_know_their_positions() {
+        let tree = example();
+        let expression = tree.child_nodes().next().unwrap();
+        assert_eq!(NodeKind::Expression, expression.kind());
+        assert_eq!(Span::new(FileId::new(1), 0..7), expression.text_range());
+        assert_eq!(NodeKind::Tree, expression.parent().unwrap().kind());
+
+        let comment = match tree.children().last().unwrap() {
+            SyntaxElement::Token(token) => token,
+            SyntaxElement::Node(_) => panic!("expected comment"),
+        };
+
+        assert!(comment.kind().is_trivia());
+        assert_eq!(Span::new(FileId::new(1), 8..14), comment.text_range());
+    }
+
+    #[test]
+    fn token_is_found_at_offset() {
+        let tree = example();
+        let token = tree.token_at_offset(3).unwrap();
+        assert_eq!(LeafKind::Token(TokenKind::PlusSign), token.kind());
+        assert_eq!(NodeKind::Expression, token.parent().kind());
+        assert!(tree.token_at_offset(14).is_none());
+    }
+}
@@ -0,0 +1,152 @@
+//! Reporting of errors and warnings found in LifeSharp source code.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::location::Span;
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+/// Indicates how serious a diagnostic is.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// Describes something that may be a mistake, but does not prevent compilation.
+    Warning,
+    /// Describes a problem that prevents compilation.
+    Error,
+}
+
+/// An error or warning associated with a location in the source code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Diagnostic {
+    /// Indicates how serious the diagnostic is.
+    pub severity: Severity,
+    /// A short identifier for the kind of diagnostic, such as `E0001`.
+    pub code: &'static str,
+    /// Describes the problem.
+    pub message: String,
+    /// The location of the problem.
+    pub span: Span,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic.
+    pub fn new(severity: Severity, code: &'static str, message: String, span: Span) -> Self {
+        Self {
+            severity,
+            code,
+            message,
+            span,
+        }
+    }
+
+    /// The key that diagnostics are sorted by: the file, then the offset, then the code.
+    ///
+    /// Diagnostics in compiler-generated code are sorted after all others.
+    #[cfg(feature = "std")]
+    fn sort_key(&self) -> (bool, u32, usize, &'static str) {
+        (
+            self.span.is_synthetic(),
+            self.span.file().index(),
+            self.span.start(),
+            self.code,
+        )
+    }
+}
+
+/// Receives diagnostics as they are produced.
+///
+/// Sinks can be shared between compiler passes running on different threads.
+pub trait DiagnosticSink: Sync {
+    /// Reports a diagnostic.
+    fn emit(&self, diagnostic: Diagnostic);
+}
+
+/// Collects diagnostics so that they can be reported in a stable order, regardless of the order that they were emitted
+/// in.
+///
+/// The diagnostics returned by [`SortedSink::finish`] are sorted by file, then by offset, then by code, with
+/// diagnostics in compiler-generated code last. Diagnostics that compare equal keep the order that they were emitted in,
+/// so passes that emit diagnostics on a single thread produce the same output every time.
+///
+/// Only available with the `std` feature, since the diagnostics are guarded by a [`Mutex`].
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct SortedSink {
+    diagnostics: Mutex<Vec<Diagnostic>>,
+}
+
+#[cfg(feature = "std")]
+impl SortedSink {
+    /// Gets the collected diagnostics in sorted order.
+    pub fn finish(self) -> Vec<Diagnostic> {
+        let mut diagnostics = self
+            .diagnostics
+            .into_inner()
+            .unwrap_or_else(|error| error.into_inner());
+        diagnostics.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        diagnostics
+    }
+}
+
+#[cfg(feature = "std")]
+impl DiagnosticSink for SortedSink {
+    fn emit(&self, diagnostic: Diagnostic) {
+        self.diagnostics
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .push(diagnostic);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::diagnostics::{Diagnostic, DiagnosticSink, Severity, SortedSink};
+    use crate::location::{FileId, Span};
+
+    fn diagnostic(code: &'static str, file: u32, start: usize) -> Diagnostic {
+        Diagnostic::new(
+            Severity::Error,
+            code,
+            String::new(),
+            Span::new(FileId::new(file), start..start),
+        )
+    }
+
+    #[test]
+    fn diagnostics_are_sorted_by_file_offset_and_code() {
+        let sink = SortedSink::default();
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| sink.emit(diagnostic("E2", 1, 0)));
+            scope.spawn(|| sink.emit(diagnostic("E1", 0, 5)));
+            scope.spawn(|| sink.emit(diagnostic("E3", 0, 5)));
+            scope.spawn(|| sink.emit(diagnostic("E1", 0, 2)));
+        });
+
+        sink.emit(Diagnostic::new(
+            Severity::Warning,
+            "W1",
+            String::new(),
+            Span::SYNTHETIC,
+        ));
+
+        let codes = sink
+            .finish()
+            .into_iter()
+            .map(|diagnostic| (diagnostic.code, diagnostic.span.start()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                ("E1", 2),
+                ("E1", 5),
+                ("E3", 5),
+                ("E2", 0),
+                ("W1", u32::MAX as usize)
+            ],
+            codes
+        );
+    }
+}
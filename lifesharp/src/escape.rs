@@ -0,0 +1,208 @@
+//! Escape sequences in character and string literals.
+//!
+//! The following escape sequences are supported:
+//! - `\n`, `\r`, and `\t` for line feeds, carriage returns, and tabs.
+//! - `\0` for the null character.
+//! - `\\`, `\'`, and `\"` for backslashes and quotation marks.
+//! - `\u{7FFF}` for any Unicode scalar value, written with one to six hexadecimal digits.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::location::Offset;
+use crate::prelude::*;
+use alloc::borrow::Cow;
+use core::fmt::Write as _;
+
+/// Error used when a literal contains an invalid escape sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum EscapeError {
+    /// Used when a backslash is followed by a character that does not start an escape sequence.
+    Unknown {
+        /// The offset of the backslash.
+        offset: Offset,
+        /// The character following the backslash.
+        character: char,
+    },
+    /// Used when a backslash is at the end of the literal.
+    Incomplete {
+        /// The offset of the backslash.
+        offset: Offset,
+    },
+    /// Used when a `\u{...}` escape sequence is not written correctly, or is not a Unicode scalar value.
+    InvalidUnicode {
+        /// The offset of the backslash.
+        offset: Offset,
+    },
+}
+
+impl core::fmt::Display for EscapeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unknown { offset, character } => write!(
+                f,
+                "unknown escape sequence \\{character} at offset {offset}"
+            ),
+            Self::Incomplete { offset } => {
+                write!(f, "incomplete escape sequence at offset {offset}")
+            }
+            Self::InvalidUnicode { offset } => {
+                write!(f, "invalid unicode escape sequence at offset {offset}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for EscapeError {}
+
+/// Returns `true` if a character should be written as an escape sequence, either because it has a special meaning in
+/// literals, or because it is invisible or changes how surrounding text is displayed.
+fn needs_escape(c: char) -> bool {
+    matches!(
+        c,
+        '\\' | '\'' | '"'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2066}'..='\u{2069}'
+            | '\u{FEFF}'
+    ) || c.is_control()
+}
+
+/// Writes a character so that it can be included in a literal, or shown to the user in a diagnostic.
+///
+/// Backslashes, quotation marks, control characters, and invisible formatting characters (such as bidirectional
+/// overrides) are written as escape sequences, all other characters are written unchanged.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EscapeDefault(char);
+
+impl core::fmt::Display for EscapeDefault {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            '\n' => f.write_str("\\n"),
+            '\r' => f.write_str("\\r"),
+            '\t' => f.write_str("\\t"),
+            '\0' => f.write_str("\\0"),
+            c @ ('\\' | '\'' | '"') => {
+                f.write_char('\\')?;
+                f.write_char(c)
+            }
+            c if needs_escape(c) => write!(f, "\\u{{{:X}}}", u32::from(c)),
+            c => f.write_char(c),
+        }
+    }
+}
+
+/// Gets a value that writes the character as it would appear in a literal.
+pub fn escape_default(c: char) -> EscapeDefault {
+    EscapeDefault(c)
+}
+
+/// Replaces the characters in a string with escape sequences as needed, returning the string unchanged if no characters
+/// needed to be escaped.
+pub fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(needs_escape) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut escaped = String::with_capacity(s.len() + 2);
+    for c in s.chars() {
+        let _ = write!(escaped, "{}", escape_default(c));
+    }
+    Cow::Owned(escaped)
+}
+
+/// Replaces the escape sequences in the contents of a literal with the characters they represent, returning the
+/// contents unchanged if they do not contain any escape sequences.
+///
+/// The offsets in any error are relative to the start of `s`.
+pub fn unescape(s: &str) -> Result<Cow<'_, str>, EscapeError> {
+    let Some(first) = s.find('\\') else {
+        return Ok(Cow::Borrowed(s));
+    };
+
+    let mut unescaped = String::with_capacity(s.len());
+    unescaped.push_str(&s[..first]);
+
+    let mut characters = s[first..].char_indices().map(|(i, c)| (first + i, c));
+    while let Some((offset, c)) = characters.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        unescaped.push(match characters.next() {
+            None => return Err(EscapeError::Incomplete { offset }),
+            Some((_, 'n')) => '\n',
+            Some((_, 'r')) => '\r',
+            Some((_, 't')) => '\t',
+            Some((_, '0')) => '\0',
+            Some((_, c @ ('\\' | '\'' | '"'))) => c,
+            Some((_, 'u')) => {
+                let invalid = || EscapeError::InvalidUnicode { offset };
+                if characters.next().map(|(_, c)| c) != Some('{') {
+                    return Err(invalid());
+                }
+
+                let mut value = 0u32;
+                let mut digits = 0;
+                loop {
+                    match characters.next().map(|(_, c)| c) {
+                        Some('}') if digits > 0 => break,
+                        Some(digit) if digits < 6 => {
+                            value = value * 16 + digit.to_digit(16).ok_or_else(invalid)?;
+                            digits += 1;
+                        }
+                        _ => return Err(invalid()),
+                    }
+                }
+
+                char::from_u32(value).ok_or_else(invalid)?
+            }
+            Some((_, character)) => return Err(EscapeError::Unknown { offset, character }),
+        });
+    }
+
+    Ok(Cow::Owned(unescaped))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::escape::{self, EscapeError};
+    use alloc::borrow::Cow;
+
+    #[test]
+    fn escaped_strings_are_unescaped_again() {
+        let original = "tab\there \"quoted\" \\ 'nul\0' \u{7} \u{202E}é";
+        let escaped = escape::escape(original);
+        assert_eq!(
+            "tab\\there \\\"quoted\\\" \\\\ \\'nul\\0\\' \\u{7} \\u{202E}é",
+            escaped
+        );
+        assert_eq!(original, escape::unescape(&escaped).unwrap());
+        assert!(matches!(escape::escape("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn invalid_escapes_are_rejected() {
+        assert_eq!(
+            Err(EscapeError::Unknown {
+                offset: 1,
+                character: 'q'
+            }),
+            escape::unescape("a\\q")
+        );
+        assert_eq!(
+            Err(EscapeError::Incomplete { offset: 2 }),
+            escape::unescape("ab\\")
+        );
+        assert_eq!(
+            Err(EscapeError::InvalidUnicode { offset: 0 }),
+            escape::unescape("\\u{D800}")
+        );
+        assert_eq!(
+            Err(EscapeError::InvalidUnicode { offset: 0 }),
+            escape::unescape("\\u{1234567}")
+        );
+        assert_eq!("\u{1F600}", escape::unescape("\\u{1F600}").unwrap());
+    }
+}
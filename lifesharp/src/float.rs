@@ -0,0 +1,170 @@
+//! Conversion between floating-point values and their representation in LifeSharp source code.
+//!
+//! Values are written using the shortest representation that parses back to the same value, so printing a literal and
+//! reading it again never changes the value. Literals have no sign, so negative values are written by negating a
+//! literal instead.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::prelude::*;
+use core::fmt::Write;
+
+/// Error used when the text of a floating-point literal is not valid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// Used when the text is not a decimal number, such as when it contains a sign or is `inf` or `NaN`.
+    Invalid(String),
+    /// Used when the value is too large to be represented.
+    OutOfRange(String),
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Invalid(text) => write!(f, "{text:?} is not a valid floating-point literal"),
+            Self::OutOfRange(text) => write!(
+                f,
+                "{text:?} is too large to be represented as a floating-point value"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Error used when a value cannot be written as a literal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum FormatError {
+    /// Used when the value is infinite or NaN.
+    NotFinite,
+    /// Used when the value is negative, including negative zero.
+    Negative,
+}
+
+impl core::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::NotFinite => "infinite and NaN values cannot be written as literals",
+            Self::Negative => "negative values cannot be written as literals",
+        })
+    }
+}
+
+impl core::error::Error for FormatError {}
+
+trait Float: core::str::FromStr + core::fmt::Debug + Copy {
+    fn is_finite(self) -> bool;
+
+    fn is_sign_negative(self) -> bool;
+}
+
+impl Float for f32 {
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    fn is_sign_negative(self) -> bool {
+        f32::is_sign_negative(self)
+    }
+}
+
+impl Float for f64 {
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    fn is_sign_negative(self) -> bool {
+        f64::is_sign_negative(self)
+    }
+}
+
+fn parse<F: Float>(text: &str) -> Result<F, ParseError> {
+    let digits = text.replace('_', "");
+    let is_decimal = digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'));
+
+    let value = match digits.parse::<F>() {
+        Ok(value) if is_decimal => value,
+        _ => return Err(ParseError::Invalid(text.to_owned())),
+    };
+
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(ParseError::OutOfRange(text.to_owned()))
+    }
+}
+
+fn write<F: Float>(output: &mut dyn Write, value: F) -> Result<(), FormatError> {
+    if !value.is_finite() {
+        return Err(FormatError::NotFinite);
+    } else if value.is_sign_negative() {
+        return Err(FormatError::Negative);
+    }
+
+    // The Debug implementation writes the shortest representation that round-trips, and always includes a decimal point
+    // or an exponent.
+    write!(output, "{:?}", value).expect("writing into a String should not fail");
+    Ok(())
+}
+
+/// Parses the text of a 32-bit floating-point literal, ignoring any `_` digit separators.
+pub fn parse_f32(text: &str) -> Result<f32, ParseError> {
+    parse(text)
+}
+
+/// Parses the text of a 64-bit floating-point literal, ignoring any `_` digit separators.
+pub fn parse_f64(text: &str) -> Result<f64, ParseError> {
+    parse(text)
+}
+
+/// Gets the shortest literal that parses back to the specified 32-bit value.
+pub fn format_f32(value: f32) -> Result<String, FormatError> {
+    let mut literal = String::new();
+    write(&mut literal, value)?;
+    Ok(literal)
+}
+
+/// Gets the shortest literal that parses back to the specified 64-bit value.
+pub fn format_f64(value: f64) -> Result<String, FormatError> {
+    let mut literal = String::new();
+    write(&mut literal, value)?;
+    Ok(literal)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float::{self, FormatError, ParseError};
+
+    #[test]
+    fn literals_are_shortest_and_look_like_floats() {
+        assert_eq!(Ok("1.0".to_owned()), float::format_f64(1.0));
+        assert_eq!(Ok("0.1".to_owned()), float::format_f32(0.1));
+        assert_eq!(Ok("1e100".to_owned()), float::format_f64(1e100));
+        assert_eq!(Err(FormatError::NotFinite), float::format_f64(f64::NAN));
+        assert_eq!(Err(FormatError::Negative), float::format_f64(-1.5));
+        assert_eq!(Err(FormatError::Negative), float::format_f32(-0.0));
+    }
+
+    #[test]
+    fn literals_are_parsed() {
+        assert_eq!(Ok(1000.5), float::parse_f64("1_000.5"));
+        assert_eq!(Ok(2.5e-3), float::parse_f32("2.5e-3"));
+        assert!(matches!(
+            float::parse_f64("inf"),
+            Err(ParseError::Invalid(_))
+        ));
+        assert!(matches!(
+            float::parse_f64("-1.0"),
+            Err(ParseError::Invalid(_))
+        ));
+        assert!(matches!(
+            float::parse_f32("1e39"),
+            Err(ParseError::OutOfRange(_))
+        ));
+    }
+}
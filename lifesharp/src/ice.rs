@@ -0,0 +1,255 @@
+//! Reporting of internal compiler errors, which are panics caused by bugs in the compiler rather than by mistakes in the
+//! code being compiled.
+//!
+//! Passes describe what they are doing with [`in_pass`], so that a panic caught by [`catch`] can be reported along with
+//! the pass and the part of the source code that was being processed, instead of only a Rust backtrace.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::location::Span;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, UnwindSafe};
+use std::sync::Once;
+
+/// The version of the compiler, included in reports so that bugs can be reproduced.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Describes what the compiler was doing when an internal compiler error occurred.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Frame {
+    /// The name of the pass, such as `lexing`.
+    pub pass: &'static str,
+    /// The part of the source code being processed by the pass, or [`Span::SYNTHETIC`] if it was processing no
+    /// particular part.
+    pub span: Span,
+}
+
+thread_local! {
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+    /// The number of passes that are currently running. Can be less than the number of [`FRAMES`] after a panic, since
+    /// the frames of the passes that panicked are kept until the panic is caught.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Installs a panic hook that removes the frames left by panics that were caught by something other than [`catch`],
+/// and then runs the hook that was installed before it.
+///
+/// This has to happen when the panic starts, since the frames of the passes that are unwinding can only be told apart
+/// from stale frames while [`DEPTH`] still counts them.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+
+    // Hooks cannot be changed while panicking.
+    if std::thread::panicking() {
+        return;
+    }
+
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = DEPTH.try_with(|depth| {
+                FRAMES.try_with(|frames| {
+                    if let Ok(mut frames) = frames.try_borrow_mut() {
+                        frames.truncate(depth.get());
+                    }
+                })
+            });
+            previous(info);
+        }));
+    });
+}
+
+/// Error used when the compiler panicked.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct InternalCompilerError {
+    /// The message that the compiler panicked with.
+    pub message: String,
+    /// The passes that were running when the compiler panicked, starting with the outermost.
+    pub frames: Vec<Frame>,
+}
+
+impl std::fmt::Display for InternalCompilerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "internal compiler error: {}\nnote: lifesharp {} encountered a bug",
+            self.message, VERSION
+        )?;
+
+        for frame in self.frames.iter().rev() {
+            write!(f, "\nnote: while {}", frame.pass)?;
+            if !frame.span.is_synthetic() {
+                write!(
+                    f,
+                    " at bytes {}..{} of file {}",
+                    frame.span.start(),
+                    frame.span.end(),
+                    frame.span.file().index()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for InternalCompilerError {}
+
+/// Runs a pass, so that an internal compiler error that occurs during it mentions the pass and the span it processes.
+pub fn in_pass<T, F: FnOnce() -> T>(pass: &'static str, span: Span, f: F) -> T {
+    struct Guard {
+        depth: usize,
+    }
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            DEPTH.with(|depth| depth.set(self.depth));
+
+            // The frame is left in place while panicking, so that it can be collected by `catch`.
+            if !std::thread::panicking() {
+                FRAMES.with(|frames| frames.borrow_mut().truncate(self.depth));
+            }
+        }
+    }
+
+    let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+    FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        // Removes any frames left by a panic that was caught by something other than `catch`.
+        frames.truncate(depth);
+        frames.push(Frame { pass, span });
+    });
+    let _guard = Guard { depth };
+    f()
+}
+
+/// Runs a closure, converting any panic that occurs into an [`InternalCompilerError`].
+///
+/// The panic hook still runs before the error is returned, so drivers should install their own hook if the default
+/// message and backtrace are not wanted. The first call installs a hook of its own that runs the previous one, so
+/// drivers that replace the hook afterwards should call the hook returned by [`std::panic::take_hook`].
+pub fn catch<T, F: FnOnce() -> T + UnwindSafe>(f: F) -> Result<T, InternalCompilerError> {
+    install_hook();
+    let depth = DEPTH.with(Cell::get);
+    let result = panic::catch_unwind(f);
+    let frames = FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        if frames.len() > depth {
+            frames.split_off(depth)
+        } else {
+            Vec::new()
+        }
+    });
+
+    result.map_err(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_owned()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("unknown panic")
+        };
+
+        InternalCompilerError { message, frames }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ice::{self, Frame, InternalCompilerError};
+    use crate::location::{FileId, Span};
+
+    #[test]
+    fn panics_are_reported_with_passes() {
+        let span = Span::new(FileId::new(2), 4..9);
+        let error = ice::catch(|| {
+            ice::in_pass("parsing", Span::SYNTHETIC, || {
+                ice::in_pass("lexing", span, || 0);
+                ice::in_pass("name resolution", span, || panic!("unresolved {}", "x"))
+            })
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            InternalCompilerError {
+                message: String::from("unresolved x"),
+                frames: vec![
+                    Frame {
+                        pass: "parsing",
+                        span: Span::SYNTHETIC
+                    },
+                    Frame {
+                        pass: "name resolution",
+                        span
+                    },
+                ],
+            },
+            error
+        );
+        assert!(error
+            .to_string()
+            .ends_with("note: while name resolution at bytes 4..9 of file 2\nnote: while parsing"));
+        assert_eq!(Ok(5), ice::catch(|| ice::in_pass("lexing", span, || 5)));
+    }
+
+    #[test]
+    fn panics_caught_elsewhere_are_forgotten() {
+        let span = Span::new(FileId::new(1), 0..3);
+        let error = ice::catch(|| {
+            ice::in_pass("parsing", span, || {
+                let _ = std::panic::catch_unwind(|| {
+                    ice::in_pass("lexing", span, || panic!("recovered"))
+                });
+                panic!("in parsing")
+            })
+        })
+        .unwrap_err();
+
+        assert_eq!("in parsing", error.message);
+        assert_eq!(
+            vec![Frame {
+                pass: "parsing",
+                span
+            }],
+            error.frames
+        );
+
+        let error = ice::catch(|| {
+            ice::in_pass("parsing", span, || {
+                let caught = std::panic::catch_unwind(|| {
+                    ice::in_pass("lexing", span, || panic!("recovered"))
+                });
+                assert!(caught.is_err());
+                ice::in_pass("name resolution", span, || panic!("unresolved"))
+            })
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            vec![
+                Frame {
+                    pass: "parsing",
+                    span
+                },
+                Frame {
+                    pass: "name resolution",
+                    span
+                },
+            ],
+            error.frames
+        );
+
+        let _ = std::panic::catch_unwind(|| ice::in_pass("lexing", span, || panic!("recovered")));
+        assert_eq!(
+            vec![Frame {
+                pass: "typing",
+                span: Span::SYNTHETIC
+            }],
+            ice::catch(|| ice::in_pass("typing", Span::SYNTHETIC, || panic!("untyped")))
+                .unwrap_err()
+                .frames
+        );
+    }
+}
@@ -2,22 +2,22 @@
 
 #![deny(missing_docs, missing_debug_implementations)]
 
+use crate::prelude::*;
 use crate::print::{Print, Printer};
-use std::borrow::Borrow;
-use std::convert::AsRef;
-use std::fmt::{Debug, Display, Formatter};
-use std::ops::Deref;
+use core::borrow::Borrow;
+use core::convert::AsRef;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Deref;
 
 /// A borrowed identifier string.
-#[derive(Eq, Hash, PartialEq)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(transparent)]
 pub struct Id(str);
 
 /// Error used when a string is not a valid identifier.
-#[derive(Clone, Debug, thiserror::Error)]
+#[derive(Clone, Debug)]
 pub enum InvalidError {
     /// Used when an identifier contains an invalid character.
-    #[error("{code_point} at index {index} is not a valid identifier character")]
     InvalidCodePoint {
         /// The invalid code point.
         code_point: char,
@@ -25,17 +25,30 @@ pub enum InvalidError {
         index: usize,
     },
     /// Used when an identifier is empty.
-    #[error("identifiers must not be empty")]
     Empty,
 }
 
+impl Display for InvalidError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidCodePoint { code_point, index } => write!(
+                f,
+                "{code_point} at index {index} is not a valid identifier character"
+            ),
+            Self::Empty => f.write_str("identifiers must not be empty"),
+        }
+    }
+}
+
+impl core::error::Error for InvalidError {}
+
 impl Id {
     /// Creates a reference to a borrowed identifier string from a reference to a borrowed UTF-8 string.
     ///
     /// # Safety
     /// Callers must ensure that the identifier string is not empty and contains valid identifier characters.
     pub unsafe fn new_unchecked(identifier: &str) -> &Self {
-        std::mem::transmute(identifier)
+        core::mem::transmute(identifier)
     }
 
     /// Creates a reference to a borrowed identifier string, checking that the string is not empty and contains valid identifier
@@ -81,19 +94,19 @@ impl<'i> TryFrom<&'i str> for &'i Id {
 }
 
 impl Debug for Id {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         Debug::fmt(&self.0, f)
     }
 }
 
 impl Display for Id {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         Display::fmt(&self.0, f)
     }
 }
 
 impl Print for Id {
-    fn print(&self, p: &mut Printer) -> std::fmt::Result {
+    fn print(&self, p: &mut Printer) -> core::fmt::Result {
         p.write_str(&self.0)
     }
 }
@@ -122,14 +135,14 @@ impl Clone for Box<Id> {
     fn clone(&self) -> Self {
         unsafe {
             // Safety: Id has same layout as str.
-            let identifier = std::mem::transmute::<&Box<Id>, &Box<str>>(self);
-            std::mem::transmute(identifier.clone())
+            let identifier = core::mem::transmute::<&Box<Id>, &Box<str>>(self);
+            core::mem::transmute(identifier.clone())
         }
     }
 }
 
 /// An owned identifier string.
-#[derive(Eq, Hash, PartialEq)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(transparent)]
 pub struct Identifier(String);
 
@@ -157,19 +170,19 @@ impl Identifier {
 }
 
 impl Debug for Identifier {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         Debug::fmt(self.as_id(), f)
     }
 }
 
 impl Display for Identifier {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         Display::fmt(self.as_id(), f)
     }
 }
 
 impl Print for Identifier {
-    fn print(&self, printer: &mut Printer) -> std::fmt::Result {
+    fn print(&self, printer: &mut Printer) -> core::fmt::Result {
         self.as_id().print(printer)
     }
 }
@@ -206,17 +219,17 @@ impl Borrow<Id> for Identifier {
     }
 }
 
-impl std::clone::Clone for Identifier {
+impl core::clone::Clone for Identifier {
     fn clone(&self) -> Self {
         Self(self.0.clone())
     }
 
     fn clone_from(&mut self, source: &Self) {
-        std::clone::Clone::clone_from(&mut self.0, &source.0)
+        core::clone::Clone::clone_from(&mut self.0, &source.0)
     }
 }
 
-impl std::borrow::ToOwned for Id {
+impl alloc::borrow::ToOwned for Id {
     type Owned = Identifier;
 
     fn to_owned(&self) -> Identifier {
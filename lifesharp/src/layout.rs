@@ -0,0 +1,194 @@
+//! Computes how values are stored in memory.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::prelude::*;
+use crate::target::Target;
+use crate::types::Primitive;
+
+/// The size and alignment of a type, in bytes.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Layout {
+    size: u64,
+    align: u64,
+}
+
+impl Layout {
+    /// The layout of a type that has no values to store, such as an empty record.
+    pub const EMPTY: Self = Self { size: 0, align: 1 };
+
+    /// Creates a layout with the specified size and alignment.
+    ///
+    /// # Panics
+    /// Panics if the alignment is not a power of two, or if the size is not a multiple of the alignment.
+    pub fn new(size: u64, align: u64) -> Self {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        assert_eq!(0, size % align, "size must be a multiple of the alignment");
+        Self { size, align }
+    }
+
+    /// Gets the layout of a primitive type on the specified target.
+    pub fn primitive(primitive: Primitive, target: &Target) -> Self {
+        let size = u64::from(primitive.target_bit_width(target)).div_ceil(8);
+        Self::new(size, size)
+    }
+
+    /// Gets the layout of a raw pointer on the specified target.
+    pub fn pointer(target: &Target) -> Self {
+        let size = target.pointer_size();
+        Self::new(size, size)
+    }
+
+    /// Gets the size of the type, which is always a multiple of its alignment.
+    pub fn size(self) -> u64 {
+        self.size
+    }
+
+    /// Gets the alignment of the type.
+    pub fn align(self) -> u64 {
+        self.align
+    }
+}
+
+fn align_to(offset: u64, align: u64) -> u64 {
+    offset.next_multiple_of(align)
+}
+
+/// The layout of a record or tuple, along with the offset of each field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RecordLayout {
+    /// The size and alignment of the record.
+    pub layout: Layout,
+    /// The offset of each field from the start of the record, in the same order as the fields were given.
+    pub offsets: Vec<u64>,
+}
+
+/// Computes the layout of a record or tuple, placing each field in order after the previous one with padding as needed.
+pub fn record(fields: &[Layout]) -> RecordLayout {
+    let mut offsets = Vec::with_capacity(fields.len());
+    let mut size = 0;
+    let mut align = 1;
+
+    for field in fields.iter() {
+        let offset = align_to(size, field.align);
+        offsets.push(offset);
+        size = offset + field.size;
+        align = align.max(field.align);
+    }
+
+    RecordLayout {
+        layout: Layout::new(align_to(size, align), align),
+        offsets,
+    }
+}
+
+/// The layout of a union, where the value of every case is stored after a tag indicating which case is in use.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct UnionLayout {
+    /// The size and alignment of the union.
+    pub layout: Layout,
+    /// The size and alignment of the tag, which is stored at offset zero, or [`Layout::EMPTY`] if there is no tag.
+    pub tag: Layout,
+    /// The offset of the value of each case from the start of the union.
+    pub value_offsets: Vec<u64>,
+}
+
+/// Computes the layout of a union from the layouts of the values of each of its cases.
+///
+/// A union with a single case has no tag, so it has the same layout as the value of that case, allowing single-case
+/// unions to wrap another type at no cost. A union with no cases has no values, so it has no tag and takes up no space.
+pub fn union(cases: &[Layout]) -> UnionLayout {
+    match cases {
+        [] => {
+            return UnionLayout {
+                layout: Layout::EMPTY,
+                tag: Layout::EMPTY,
+                value_offsets: Vec::new(),
+            }
+        }
+        [case] => {
+            return UnionLayout {
+                layout: *case,
+                tag: Layout::EMPTY,
+                value_offsets: vec![0],
+            }
+        }
+        _ => (),
+    }
+
+    let tag_size = match cases.len() {
+        2..=0x100 => 1,
+        0x101..=0x1_0000 => 2,
+        _ => 4,
+    };
+
+    let tag = Layout::new(tag_size, tag_size);
+    let mut size = tag.size;
+    let mut align = tag.align;
+    let mut value_offsets = Vec::with_capacity(cases.len());
+
+    for case in cases.iter() {
+        let offset = align_to(tag.size, case.align);
+        value_offsets.push(offset);
+        size = size.max(offset + case.size);
+        align = align.max(case.align);
+    }
+
+    UnionLayout {
+        layout: Layout::new(align_to(size, align), align),
+        tag,
+        value_offsets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{self, Layout};
+    use crate::target::Target;
+    use crate::types::Primitive;
+
+    #[test]
+    fn record_fields_are_padded() {
+        let target = Target::from_triple("x86_64-unknown-linux-gnu").unwrap();
+        let fields = [
+            Layout::primitive(Primitive::U8, &target),
+            Layout::primitive(Primitive::U32, &target),
+            Layout::primitive(Primitive::Bool, &target),
+        ];
+
+        let record = layout::record(&fields);
+        assert_eq!(vec![0, 4, 8], record.offsets);
+        assert_eq!(Layout::new(12, 4), record.layout);
+        assert_eq!(Layout::EMPTY, layout::record(&[]).layout);
+    }
+
+    #[test]
+    fn union_values_follow_tag() {
+        let target = Target::from_triple("x86_64-unknown-linux-gnu").unwrap();
+        let cases = [Layout::EMPTY, Layout::pointer(&target)];
+        let union = layout::union(&cases);
+        assert_eq!(Layout::new(1, 1), union.tag);
+        assert_eq!(vec![1, 8], union.value_offsets);
+        assert_eq!(Layout::new(16, 8), union.layout);
+    }
+
+    #[test]
+    fn single_case_wrappers_have_layout_of_value() {
+        let target = Target::from_triple("x86_64-unknown-linux-gnu").unwrap();
+        let value = Layout::primitive(Primitive::U32, &target);
+
+        let union = layout::union(&[value]);
+        assert_eq!((value, Layout::EMPTY), (union.layout, union.tag));
+        assert_eq!(vec![0], union.value_offsets);
+        assert_eq!(value, layout::record(&[value]).layout);
+    }
+
+    #[test]
+    fn empty_unions_have_no_tag() {
+        let union = layout::union(&[]);
+        assert_eq!((Layout::EMPTY, Layout::EMPTY), (union.layout, union.tag));
+        assert!(union.value_offsets.is_empty());
+    }
+}
@@ -3,9 +3,13 @@
 #![deny(missing_docs)]
 
 use crate::location;
-use std::convert::Infallible;
+use crate::prelude::*;
+use core::convert::Infallible;
 
-/// Buffer used to store a [`String`] without line feed (`\n`) or carriage return (`\r`) characters.
+/// Buffer used to store a line of source code without its line terminator.
+///
+/// The line never contains a line feed (`\n`). A carriage return (`\r`) is only removed when it comes before a line
+/// feed, so a lone carriage return, or one at the end of the last line, is kept as part of the line.
 #[derive(Debug)]
 #[repr(transparent)]
 pub struct LineBuffer<'a>(&'a mut String); // TODO: Could have field for column number.
@@ -14,10 +18,10 @@ impl LineBuffer<'_> {
     /// Appends a character to the buffer.
     ///
     /// # Panics
-    /// Panics if a line feed (`\n`) or carriage return (`\r`) is appended.
+    /// Panics if a line feed (`\n`) is appended.
     pub fn push(&mut self, c: char) {
         match c {
-            '\n' | '\r' => panic!("no newline characters are allowed"),
+            '\n' => panic!("no newline characters are allowed"),
             _ => self.0.push(c),
         }
     }
@@ -25,10 +29,12 @@ impl LineBuffer<'_> {
 
 /// Indicates whether an [`Input`] still has lines to be read.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-#[repr(u8)]
 pub enum Continue {
-    /// Indicates that more lines will follow.
-    More,
+    /// Indicates that a line was read, followed by a line terminator that was removed from the line.
+    ///
+    /// The length of the line terminator, in bytes, is `1` for a line feed (`\n`), `2` for a carriage return followed
+    /// by a line feed (`\r\n`), and `0` if the line is the last line and does not end with a line terminator.
+    More(usize),
     /// Indicates that the end of the file has been reached.
     End,
 }
@@ -42,31 +48,106 @@ pub trait Input {
     fn next_line<'a>(&mut self, buffer: LineBuffer<'a>) -> Result<Continue, Self::Error>;
 }
 
-impl Input for std::str::Lines<'_> {
-    type Error = Infallible;
+/// Removes a line feed (`\n`) or a carriage return followed by a line feed (`\r\n`) from the end of a line, returning
+/// the number of bytes that were removed.
+fn remove_line_terminator(line: &mut String, start: usize) -> usize {
+    if !line[start..].ends_with('\n') {
+        return 0;
+    }
 
-    fn next_line<'a>(&mut self, buffer: LineBuffer<'a>) -> Result<Continue, Self::Error> {
-        if let Some(line) = self.next() {
-            buffer.0.push_str(line);
-            Ok(Continue::More)
-        } else {
-            Ok(Continue::End)
-        }
+    line.pop();
+    if line[start..].ends_with('\r') {
+        line.pop();
+        2
+    } else {
+        1
+    }
+}
+
+/// Reads lines from a buffered reader, such as a file.
+///
+/// Lines are separated in the same way as in [`Chunks`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Reader<B>(B);
+
+#[cfg(feature = "std")]
+impl<B: std::io::BufRead> Reader<B> {
+    /// Creates an input that reads lines from the reader.
+    pub fn new(reader: B) -> Self {
+        Self(reader)
     }
 }
 
-impl<B: std::io::BufRead> Input for std::io::Lines<B> {
+#[cfg(feature = "std")]
+impl<B: std::io::BufRead> Input for Reader<B> {
     type Error = std::io::Error;
 
     fn next_line<'a>(&mut self, buffer: LineBuffer<'a>) -> std::io::Result<Continue> {
-        match self.next() {
-            Some(Ok(line)) => {
-                buffer.0.push_str(line.as_str());
-                Ok(Continue::More)
+        let start = buffer.0.len();
+        if self.0.read_line(buffer.0)? == 0 {
+            Ok(Continue::End)
+        } else {
+            Ok(Continue::More(remove_line_terminator(buffer.0, start)))
+        }
+    }
+}
+
+/// Reads lines from a sequence of string chunks, such as the pieces of a rope used by an editor to store a document,
+/// without first copying the chunks into a single [`String`].
+///
+/// Lines are separated by line feeds (`\n`), and a carriage return (`\r`) before a line feed is removed, as in
+/// [`str::lines`]. Lines and line terminators can be split across any number of chunks.
+#[derive(Clone, Debug)]
+pub struct Chunks<'c, I> {
+    chunks: I,
+    current: &'c str,
+}
+
+impl<'c, I: Iterator<Item = &'c str>> Chunks<'c, I> {
+    /// Creates an input that reads lines from the chunks, in order.
+    pub fn new<C: IntoIterator<IntoIter = I>>(chunks: C) -> Self {
+        Self {
+            chunks: chunks.into_iter(),
+            current: "",
+        }
+    }
+}
+
+impl<'c, I: Iterator<Item = &'c str>> Input for Chunks<'c, I> {
+    type Error = Infallible;
+
+    fn next_line<'a>(&mut self, buffer: LineBuffer<'a>) -> Result<Continue, Self::Error> {
+        let start = buffer.0.len();
+        let mut read_any = false;
+
+        loop {
+            if self.current.is_empty() {
+                match self.chunks.next() {
+                    Some(chunk) => {
+                        self.current = chunk;
+                        continue;
+                    }
+                    None => break,
+                }
             }
-            Some(Err(error)) => Err(error),
-            None => Ok(Continue::End),
+
+            read_any = true;
+            if let Some(end) = self.current.find('\n') {
+                buffer.0.push_str(&self.current[..=end]);
+                self.current = &self.current[end + 1..];
+                return Ok(Continue::More(remove_line_terminator(buffer.0, start)));
+            }
+
+            buffer.0.push_str(self.current);
+            self.current = "";
         }
+
+        Ok(if read_any {
+            Continue::More(0)
+        } else {
+            Continue::End
+        })
     }
 }
 
@@ -88,18 +169,19 @@ impl<I: Input> InputSource for I {
 }
 
 impl<'a> InputSource for &'a str {
-    type IntoInput = std::str::Lines<'a>;
+    type IntoInput = Chunks<'a, core::iter::Once<&'a str>>;
 
     fn into_input(self) -> Self::IntoInput {
-        self.lines()
+        Chunks::new(core::iter::once(self))
     }
 }
 
+#[cfg(feature = "std")]
 impl InputSource for std::fs::File {
-    type IntoInput = std::io::Lines<std::io::BufReader<Self>>;
+    type IntoInput = Reader<std::io::BufReader<Self>>;
 
     fn into_input(self) -> Self::IntoInput {
-        std::io::BufRead::lines(std::io::BufReader::new(self))
+        Reader::new(std::io::BufReader::new(self))
     }
 }
 
@@ -121,18 +203,68 @@ impl<'b, I: Input> Wrapper<'b, I> {
         }
     }
 
+    /// Reads the next line, returning its text, its line number, and the length of the line terminator that followed it.
     pub(super) fn next_line(
         &mut self,
-    ) -> Result<Option<(&str, location::Number)>, <I as Input>::Error> {
+    ) -> Result<Option<(&str, location::Number, usize)>, <I as Input>::Error> {
         self.buffer.clear();
 
         Ok(match self.input.next_line(LineBuffer(self.buffer))? {
-            Continue::More => {
+            Continue::More(terminator_length) => {
                 let line_number = self.current_line;
                 location::increment_number(&mut self.current_line);
-                Some((self.buffer.as_str(), line_number))
+                Some((self.buffer.as_str(), line_number, terminator_length))
             }
             Continue::End => None,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::input::{Chunks, Continue, Input, LineBuffer};
+
+    fn lines<I: Input>(mut input: I) -> Vec<(String, usize)>
+    where
+        I::Error: core::fmt::Debug,
+    {
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            match input.next_line(LineBuffer(&mut line)).unwrap() {
+                Continue::More(terminator_length) => lines.push((line, terminator_length)),
+                Continue::End => return lines,
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_are_split_into_lines() {
+        let chunks = ["fi", "", "rst\r", "\nsec\n", "\nthi", "rd\r"];
+        assert_eq!(
+            vec![
+                (String::from("first"), 2),
+                (String::from("sec"), 1),
+                (String::new(), 1),
+                (String::from("third\r"), 0)
+            ],
+            lines(Chunks::new(chunks))
+        );
+        assert!(lines(Chunks::new(["", ""])).is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn readers_are_split_into_lines() {
+        let reader = crate::lexer::input::Reader::new(std::io::Cursor::new("a\r\nb\n\nc"));
+        assert_eq!(
+            vec![
+                (String::from("a"), 2),
+                (String::from("b"), 1),
+                (String::new(), 1),
+                (String::from("c"), 0)
+            ],
+            lines(reader)
+        );
+    }
+}
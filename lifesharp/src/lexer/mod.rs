@@ -1,19 +1,21 @@
 //! Tokenization of LifeSharp source code.
 
 use crate::identifier::Identifier;
-use crate::location::{self, Location, OffsetRange};
+use crate::location::{self, FileId, Span};
+use crate::prelude::*;
 use crate::print;
-use typed_arena::Arena;
 
 mod input;
 
-pub use input::{Continue, Input, InputSource};
+#[cfg(feature = "std")]
+pub use input::Reader;
+pub use input::{Chunks, Continue, Input, InputSource};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[repr(transparent)]
 pub struct LiteralString(String);
 
-impl std::ops::Deref for LiteralString {
+impl core::ops::Deref for LiteralString {
     type Target = String;
 
     fn deref(&self) -> &String {
@@ -21,7 +23,7 @@ impl std::ops::Deref for LiteralString {
     }
 }
 
-impl std::ops::DerefMut for LiteralString {
+impl core::ops::DerefMut for LiteralString {
     fn deref_mut(&mut self) -> &mut String {
         &mut self.0
     }
@@ -36,7 +38,8 @@ impl From<String> for LiteralString {
 impl print::Print for LiteralString {
     fn print(&self, printer: &mut print::Printer) -> print::Result {
         printer.write_char('\'')?;
-        todo!("write characters");
+
+        printer.write_str(&crate::escape::escape(&self.0))?;
         printer.write_char('\'')
     }
 }
@@ -63,9 +66,27 @@ pub enum Token<'l> {
     Semicolon,
     ForwardSlash,
     Period,
+    /// Indicates a range that excludes its end (e.g. `0 .. n`).
+    DoublePeriod,
+    /// Indicates a range that includes its end (e.g. `0 ..= n`).
+    DoublePeriodEquals,
     Equals,
     Ampersand,
     VerticalBar,
+    /// The short-circuiting logical and operator (`&&`).
+    DoubleAmpersand,
+    /// The short-circuiting logical or operator (`||`).
+    DoubleVerticalBar,
+    /// The bitwise and operator (`&&&`).
+    TripleAmpersand,
+    /// The bitwise or operator (`|||`).
+    TripleVerticalBar,
+    /// The bitwise exclusive or operator (`^^^`).
+    TripleCaret,
+    /// The left shift operator (`<<<`).
+    TripleLessThan,
+    /// The right shift operator (`>>>`).
+    TripleGreaterThan,
     /// Used to indicate the type of something, such as a local variable (e.g. `let x: u32`), parameter, or return type.
     Colon,
     /// Used to denote an item within a path, such as in `some\modules\containing::MyType`, where semicolons indicate that
@@ -83,9 +104,11 @@ pub enum Token<'l> {
     KeywordUse,
     /// The `type` keyword indicates the start of a type definition.
     KeywordType,
-    //And, // TODO: How will bitwise operators be represented?
-    //Not,
-    //Or,
+    /// The logical negation operator (`not`).
+    KeywordNot,
+    /// Marks functions and blocks that can perform operations the compiler cannot check, such as dereferencing raw
+    /// pointers (`unsafe`).
+    KeywordUnsafe,
     LiteralCharacter(char),
     LiteralString(&'l LiteralString),
     LiteralBoolean(bool),
@@ -94,12 +117,445 @@ pub enum Token<'l> {
     LifetimeParameter(&'l Identifier),
 }
 
+/// The kind of a token, without the contents of identifiers and literals.
+///
+/// The contents are stored separately in [`Output`], so that the list of tokens in a source file is compact and cheap to
+/// copy.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum TokenKind {
+    Dedent,
+    Indent,
+    OpenCurlyBrace,
+    CloseCurlyBrace,
+    OpenParenthesis,
+    CloseParenthesis,
+    OpenSquareBracket,
+    CloseSquareBracket,
+    LessThan,
+    GreaterThan,
+    /// Used as the path separator (e.g. `some\path\to::SomeType`).
+    BackwardSlash,
+    PlusSign,
+    MinusSign,
+    Asterisk,
+    /// Used to separate things on the same line.
+    Semicolon,
+    ForwardSlash,
+    Period,
+    /// Indicates a range that excludes its end (e.g. `0 .. n`).
+    DoublePeriod,
+    /// Indicates a range that includes its end (e.g. `0 ..= n`).
+    DoublePeriodEquals,
+    Equals,
+    Ampersand,
+    VerticalBar,
+    /// The short-circuiting logical and operator (`&&`).
+    DoubleAmpersand,
+    /// The short-circuiting logical or operator (`||`).
+    DoubleVerticalBar,
+    /// The bitwise and operator (`&&&`).
+    TripleAmpersand,
+    /// The bitwise or operator (`|||`).
+    TripleVerticalBar,
+    /// The bitwise exclusive or operator (`^^^`).
+    TripleCaret,
+    /// The left shift operator (`<<<`).
+    TripleLessThan,
+    /// The right shift operator (`>>>`).
+    TripleGreaterThan,
+    /// Used to indicate the type of something, such as a local variable (e.g. `let x: u32`), parameter, or return type.
+    Colon,
+    /// Used to denote an item within a path, such as in `some\modules\containing::MyType`, where semicolons indicate that
+    /// `MyType` is the name of a type.
+    DoubleColon,
+    /// The assignment operator (`<-`) writes a value to a memory location.
+    Assignment,
+    /// Indicates the return value of an anonymous function (`fun (x: u32) -> x + 1u32`).
+    LambdaReturn,
+    /// The `def` keyword indicates the start of a function definition.
+    KeywordDef,
+    /// Indicates the start of an anonymous function (`fun`).
+    KeywordFun,
+    /// The `use` keyword brings items within a path into scope.
+    KeywordUse,
+    /// The `type` keyword indicates the start of a type definition.
+    KeywordType,
+    /// The logical negation operator (`not`).
+    KeywordNot,
+    /// Marks functions and blocks that can perform operations the compiler cannot check, such as dereferencing raw
+    /// pointers (`unsafe`).
+    KeywordUnsafe,
+    LiteralCharacter,
+    LiteralString,
+    LiteralBoolean,
+    Identifier,
+    TypeParameter,
+    LifetimeParameter,
+}
+
+/// Describes the kind of a token, used when highlighting source code.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum TokenClass {
+    /// Tokens such as indentation that have no text of their own.
+    Layout,
+    /// Brackets, separators, and path delimiters.
+    Punctuation,
+    /// Arithmetic, comparison, and assignment operators.
+    Operator,
+    /// Keywords such as `def` or `type`.
+    Keyword,
+    /// Character, string, and boolean literals.
+    Literal,
+    /// Identifiers and the names of generic parameters.
+    Identifier,
+}
+
+impl TokenKind {
+    /// Gets the class of this token, used when highlighting source code.
+    pub fn class(self) -> TokenClass {
+        match self {
+            Self::Dedent | Self::Indent => TokenClass::Layout,
+            Self::OpenCurlyBrace
+            | Self::CloseCurlyBrace
+            | Self::OpenParenthesis
+            | Self::CloseParenthesis
+            | Self::OpenSquareBracket
+            | Self::CloseSquareBracket
+            | Self::BackwardSlash
+            | Self::Semicolon
+            | Self::Period
+            | Self::Colon
+            | Self::DoubleColon => TokenClass::Punctuation,
+            Self::LessThan
+            | Self::GreaterThan
+            | Self::DoublePeriod
+            | Self::DoublePeriodEquals
+            | Self::PlusSign
+            | Self::MinusSign
+            | Self::Asterisk
+            | Self::ForwardSlash
+            | Self::Equals
+            | Self::Ampersand
+            | Self::VerticalBar
+            | Self::DoubleAmpersand
+            | Self::DoubleVerticalBar
+            | Self::TripleAmpersand
+            | Self::TripleVerticalBar
+            | Self::TripleCaret
+            | Self::TripleLessThan
+            | Self::TripleGreaterThan
+            | Self::Assignment
+            | Self::LambdaReturn => TokenClass::Operator,
+            Self::KeywordDef
+            | Self::KeywordFun
+            | Self::KeywordUse
+            | Self::KeywordType
+            | Self::KeywordNot
+            | Self::KeywordUnsafe => TokenClass::Keyword,
+            Self::LiteralCharacter | Self::LiteralString | Self::LiteralBoolean => {
+                TokenClass::Literal
+            }
+            Self::Identifier | Self::TypeParameter | Self::LifetimeParameter => {
+                TokenClass::Identifier
+            }
+        }
+    }
+}
+
+/// The contents of an identifier or literal token.
+#[derive(Clone, Copy, Debug)]
+enum Payload<'l> {
+    Character(char),
+    String(&'l LiteralString),
+    Boolean(bool),
+    Identifier(&'l Identifier),
+}
+
+impl<'l> Token<'l> {
+    /// Gets the kind of this token.
+    pub fn kind(&self) -> TokenKind {
+        self.clone().into_parts().0
+    }
+
+    /// Gets the class of this token, used when highlighting source code.
+    pub fn class(&self) -> TokenClass {
+        self.kind().class()
+    }
+
+    fn into_parts(self) -> (TokenKind, Option<Payload<'l>>) {
+        macro_rules! simple_tokens {
+            ($($name: ident),*) => {
+                match self {
+                    $(Self::$name => (TokenKind::$name, None),)*
+                    Self::LiteralCharacter(c) => (TokenKind::LiteralCharacter, Some(Payload::Character(c))),
+                    Self::LiteralString(s) => (TokenKind::LiteralString, Some(Payload::String(s))),
+                    Self::LiteralBoolean(b) => (TokenKind::LiteralBoolean, Some(Payload::Boolean(b))),
+                    Self::Identifier(name) => (TokenKind::Identifier, Some(Payload::Identifier(name))),
+                    Self::TypeParameter(name) => (TokenKind::TypeParameter, Some(Payload::Identifier(name))),
+                    Self::LifetimeParameter(name) => {
+                        (TokenKind::LifetimeParameter, Some(Payload::Identifier(name)))
+                    }
+                }
+            };
+        }
+
+        simple_tokens!(
+            Dedent,
+            Indent,
+            OpenCurlyBrace,
+            CloseCurlyBrace,
+            OpenParenthesis,
+            CloseParenthesis,
+            OpenSquareBracket,
+            CloseSquareBracket,
+            LessThan,
+            GreaterThan,
+            BackwardSlash,
+            PlusSign,
+            MinusSign,
+            Asterisk,
+            Semicolon,
+            ForwardSlash,
+            Period,
+            DoublePeriod,
+            DoublePeriodEquals,
+            Equals,
+            Ampersand,
+            VerticalBar,
+            DoubleAmpersand,
+            DoubleVerticalBar,
+            TripleAmpersand,
+            TripleVerticalBar,
+            TripleCaret,
+            TripleLessThan,
+            TripleGreaterThan,
+            Colon,
+            DoubleColon,
+            Assignment,
+            LambdaReturn,
+            KeywordDef,
+            KeywordFun,
+            KeywordUse,
+            KeywordType,
+            KeywordNot,
+            KeywordUnsafe
+        )
+    }
+
+    fn from_parts(kind: TokenKind, payload: Option<Payload<'l>>) -> Option<Self> {
+        macro_rules! simple_tokens {
+            ($($name: ident),*) => {
+                Some(match (kind, payload) {
+                    $((TokenKind::$name, None) => Self::$name,)*
+                    (TokenKind::LiteralCharacter, Some(Payload::Character(c))) => Self::LiteralCharacter(c),
+                    (TokenKind::LiteralString, Some(Payload::String(s))) => Self::LiteralString(s),
+                    (TokenKind::LiteralBoolean, Some(Payload::Boolean(b))) => Self::LiteralBoolean(b),
+                    (TokenKind::Identifier, Some(Payload::Identifier(name))) => Self::Identifier(name),
+                    (TokenKind::TypeParameter, Some(Payload::Identifier(name))) => Self::TypeParameter(name),
+                    (TokenKind::LifetimeParameter, Some(Payload::Identifier(name))) => {
+                        Self::LifetimeParameter(name)
+                    }
+                    _ => return None,
+                })
+            };
+        }
+
+        simple_tokens!(
+            Dedent,
+            Indent,
+            OpenCurlyBrace,
+            CloseCurlyBrace,
+            OpenParenthesis,
+            CloseParenthesis,
+            OpenSquareBracket,
+            CloseSquareBracket,
+            LessThan,
+            GreaterThan,
+            BackwardSlash,
+            PlusSign,
+            MinusSign,
+            Asterisk,
+            Semicolon,
+            ForwardSlash,
+            Period,
+            DoublePeriod,
+            DoublePeriodEquals,
+            Equals,
+            Ampersand,
+            VerticalBar,
+            DoubleAmpersand,
+            DoubleVerticalBar,
+            TripleAmpersand,
+            TripleVerticalBar,
+            TripleCaret,
+            TripleLessThan,
+            TripleGreaterThan,
+            Colon,
+            DoubleColon,
+            Assignment,
+            LambdaReturn,
+            KeywordDef,
+            KeywordFun,
+            KeywordUse,
+            KeywordType,
+            KeywordNot,
+            KeywordUnsafe
+        )
+    }
+}
+
+impl print::Print for Token<'_> {
+    fn print(&self, printer: &mut print::Printer) -> print::Result {
+        match self {
+            Self::Dedent | Self::Indent => Ok(()),
+            Self::OpenCurlyBrace => printer.write_char('{'),
+            Self::CloseCurlyBrace => printer.write_char('}'),
+            Self::OpenParenthesis => printer.write_char('('),
+            Self::CloseParenthesis => printer.write_char(')'),
+            Self::OpenSquareBracket => printer.write_char('['),
+            Self::CloseSquareBracket => printer.write_char(']'),
+            Self::LessThan => printer.write_char('<'),
+            Self::GreaterThan => printer.write_char('>'),
+            Self::BackwardSlash => printer.write_char('\\'),
+            Self::PlusSign => printer.write_char('+'),
+            Self::MinusSign => printer.write_char('-'),
+            Self::Asterisk => printer.write_char('*'),
+            Self::Semicolon => printer.write_char(';'),
+            Self::ForwardSlash => printer.write_char('/'),
+            Self::Period => printer.write_char('.'),
+            Self::DoublePeriod => printer.write_str(".."),
+            Self::DoublePeriodEquals => printer.write_str("..="),
+            Self::Equals => printer.write_char('='),
+            Self::Ampersand => printer.write_char('&'),
+            Self::VerticalBar => printer.write_char('|'),
+            Self::DoubleAmpersand => printer.write_str("&&"),
+            Self::DoubleVerticalBar => printer.write_str("||"),
+            Self::TripleAmpersand => printer.write_str("&&&"),
+            Self::TripleVerticalBar => printer.write_str("|||"),
+            Self::TripleCaret => printer.write_str("^^^"),
+            Self::TripleLessThan => printer.write_str("<<<"),
+            Self::TripleGreaterThan => printer.write_str(">>>"),
+            Self::Colon => printer.write_char(':'),
+            Self::DoubleColon => printer.write_str("::"),
+            Self::Assignment => printer.write_str("<-"),
+            Self::LambdaReturn => printer.write_str("->"),
+            Self::KeywordDef => printer.write_str("def"),
+            Self::KeywordFun => printer.write_str("fun"),
+            Self::KeywordUse => printer.write_str("use"),
+            Self::KeywordType => printer.write_str("type"),
+            Self::KeywordNot => printer.write_str("not"),
+            Self::KeywordUnsafe => printer.write_str("unsafe"),
+            Self::LiteralCharacter(c) => write!(printer, "'{}'", crate::escape::escape_default(*c)),
+            Self::LiteralString(literal) => literal.print(printer),
+            Self::LiteralBoolean(value) => printer.write_str(if *value { "true" } else { "false" }),
+            Self::Identifier(identifier) => identifier.print(printer),
+            Self::TypeParameter(name) => {
+                printer.write_char('\'')?;
+                name.print(printer)
+            }
+            Self::LifetimeParameter(name) => {
+                printer.write_char('~')?;
+                name.print(printer)
+            }
+        }
+    }
+}
+
+crate::print_display_impl!(Token<'_>);
+
+/// Stores the kinds, start offsets, and lengths of tokens in separate arrays, with the contents of identifiers and
+/// literals kept in a side table sorted by the index of the token they belong to.
+#[derive(Clone, Debug, Default)]
+struct Buffer<'o> {
+    file: FileId,
+    kinds: Vec<TokenKind>,
+    starts: Vec<u32>,
+    lengths: Vec<u32>,
+    payloads: Vec<(u32, Payload<'o>)>,
+}
+
+impl<'o> Buffer<'o> {
+    fn push(&mut self, token: Token<'o>, span: Span) {
+        let (kind, payload) = token.into_parts();
+        if let Some(payload) = payload {
+            let index = u32::try_from(self.kinds.len()).expect("too many tokens");
+            self.payloads.push((index, payload));
+        }
+
+        let start = u32::try_from(span.start()).expect("span should fit in 32 bits");
+        let length = u32::try_from(span.len()).expect("span should fit in 32 bits");
+        self.kinds.push(kind);
+        self.starts.push(start);
+        self.lengths.push(length);
+    }
+
+    fn clear(&mut self, file: FileId) {
+        self.file = file;
+        self.kinds.clear();
+        self.starts.clear();
+        self.lengths.clear();
+        self.payloads.clear();
+    }
+
+    fn span(&self, index: usize) -> Span {
+        let start = self.starts[index] as usize;
+        Span::new(self.file, start..start + self.lengths[index] as usize)
+    }
+
+    fn token(&self, index: usize) -> Option<Token<'o>> {
+        let kind = *self.kinds.get(index)?;
+        let payload = u32::try_from(index).ok().and_then(|index| {
+            let position = self
+                .payloads
+                .binary_search_by_key(&index, |(token_index, _)| *token_index)
+                .ok()?;
+            Some(self.payloads[position].1)
+        });
+
+        Token::from_parts(kind, payload)
+    }
+}
+
+/// Refers to a token in the [`Output`] of the lexer.
+#[derive(Clone, Copy, Debug)]
+pub struct TokenRef<'a, 'o> {
+    tokens: &'a Buffer<'o>,
+    index: usize,
+}
+
+impl<'o> TokenRef<'_, 'o> {
+    /// Gets the index of the token.
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Gets the kind of the token.
+    pub fn kind(self) -> TokenKind {
+        self.tokens.kinds[self.index]
+    }
+
+    /// Gets the location of the token.
+    pub fn span(self) -> Span {
+        self.tokens.span(self.index)
+    }
+
+    /// Gets the token, including the contents of identifiers and literals.
+    pub fn token(self) -> Token<'o> {
+        self.tokens
+            .token(self.index)
+            .expect("payload should match token kind")
+    }
+}
+
 /// Allows the reuse of some objects allocated during tokenization.
 #[derive(Debug, Default)]
 pub struct Cache<'o> {
-    //buffer: std::cell::RefCell<Buffer>, // Allows dropping of all buffers when tokenization is done.
+    //buffer: core::cell::RefCell<Buffer>, // Allows dropping of all buffers when tokenization is done.
     line_buffer: String,
-    tokens: Vec<(Token<'o>, OffsetRange)>,
+    tokens: Buffer<'o>,
     //locations:
     //literal_strings: Arena<LiteralString>,
     //identifiers: Arena<Identifier>,
@@ -107,32 +563,124 @@ pub struct Cache<'o> {
 
 #[derive(Debug, Default)]
 pub struct Output<'o> {
-    tokens: Box<[(Token<'o>, OffsetRange)]>,
+    tokens: Buffer<'o>,
     //literal_strings: Arena<LiteralString>,
     //identifiers: Arena<Identifier>,
-    locations: (), //LocationMap,
+    locations: location::Map,
 }
 
-impl Output<'_> {
-    /// Gets the tokens from the source file.
-    pub fn tokens(&self) -> &[(Token<'_>, OffsetRange)] {
-        &self.tokens
+impl<'o> Output<'o> {
+    /// Gets the number of tokens.
+    pub fn len(&self) -> usize {
+        self.tokens.kinds.len()
+    }
+
+    /// Returns `true` if the source file did not contain any tokens.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.kinds.is_empty()
     }
 
-    //pub fn locations(&self) -> &LocationMap
+    /// Gets the kinds of the tokens from the source file.
+    pub fn kinds(&self) -> &[TokenKind] {
+        &self.tokens.kinds
+    }
 
-    //pub fn located_tokens
+    /// Gets the token at the specified index.
+    pub fn get(&self, index: usize) -> Option<TokenRef<'_, 'o>> {
+        if index < self.len() {
+            Some(TokenRef {
+                tokens: &self.tokens,
+                index,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over references to the tokens from the source file.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = TokenRef<'_, 'o>> + '_ {
+        (0..self.len()).map(|index| TokenRef {
+            tokens: &self.tokens,
+            index,
+        })
+    }
+
+    /// Gets the tokens from the source file, along with their locations.
+    pub fn tokens(&self) -> impl ExactSizeIterator<Item = (Token<'o>, Span)> + '_ {
+        self.iter().map(|token| (token.token(), token.span()))
+    }
+
+    /// Gets the line and column numbers of the tokens.
+    pub fn locations(&self) -> &location::Map {
+        &self.locations
+    }
+}
+
+#[cfg(feature = "stats")]
+impl crate::memory::MemoryUsage for Output<'_> {
+    fn memory_usage(&self, report: &mut crate::memory::Report) {
+        report.record_vec("lexer.kinds", &self.tokens.kinds);
+        report.record_vec("lexer.starts", &self.tokens.starts);
+        report.record_vec("lexer.lengths", &self.tokens.lengths);
+        report.record_vec("lexer.payloads", &self.tokens.payloads);
+        self.locations.memory_usage(report);
+    }
+}
+
+/// Error used when source code could not be tokenized.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error<E> {
+    /// Used when the source code could not be read.
+    Input(E),
+    /// Used when a character does not start any token.
+    UnexpectedCharacter {
+        /// The character that was not expected.
+        character: char,
+        /// The location of the character.
+        span: Span,
+    },
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(error: E) -> Self {
+        Self::Input(error)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Input(error) => core::fmt::Display::fmt(error, f),
+            Self::UnexpectedCharacter { character, span } => write!(
+                f,
+                "unexpected character {character:?} at bytes {}..{}",
+                span.start(),
+                span.end()
+            ),
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Input(error) => Some(error),
+            Self::UnexpectedCharacter { .. } => None,
+        }
+    }
 }
 
 pub fn tokenize<'o, S: InputSource>(
     source: S,
+    file: FileId,
     cache: Option<&mut Cache<'o>>,
-) -> Result<Output<'o>, <<S as InputSource>::IntoInput as Input>::Error> {
+) -> Result<Output<'o>, Error<<<S as InputSource>::IntoInput as Input>::Error>> {
     let mut owned_line_buffer;
     let line_buffer: &mut String;
 
     let mut owned_tokens;
-    let tokens: &mut Vec<(Token<'o>, OffsetRange)>;
+    let tokens: &mut Buffer<'o>;
 
     if let Some(Cache {
         line_buffer: ref mut previous_line_buffer,
@@ -141,7 +689,7 @@ pub fn tokenize<'o, S: InputSource>(
     {
         line_buffer = previous_line_buffer;
 
-        previous_tokens.clear();
+        previous_tokens.clear(file);
         tokens = previous_tokens;
 
         //previous_output.locations.clear();
@@ -149,19 +697,22 @@ pub fn tokenize<'o, S: InputSource>(
         owned_line_buffer = String::default();
         line_buffer = &mut owned_line_buffer;
 
-        owned_tokens = Vec::default();
+        owned_tokens = Buffer {
+            file,
+            ..Buffer::default()
+        };
         tokens = &mut owned_tokens;
     }
 
     let mut input = input::Wrapper::new(source, line_buffer);
+    let mut locations = location::Map::default();
     let mut next_byte_offset: location::Offset = 0;
-    let mut current_indent_level = 0u64;
 
     /// Allows reading of characters from a line of source code, automatically counting position information and allowing
     /// backtracking.
     #[derive(Clone)]
     struct LineCharacters<'a> {
-        remaining: std::str::Chars<'a>,
+        remaining: core::str::Chars<'a>,
         column_number: location::Number,
         byte_offset: location::Offset,
     }
@@ -181,7 +732,7 @@ pub fn tokenize<'o, S: InputSource>(
             let byte_offset = self.byte_offset;
             let mut next_characters = Self {
                 remaining,
-                byte_offset: self.byte_offset + 1,
+                byte_offset: self.byte_offset + next.len_utf8(),
                 ..self.clone()
             };
 
@@ -190,24 +741,38 @@ pub fn tokenize<'o, S: InputSource>(
         }
     }
 
-    while let Some((current_line, line_number)) = input.next_line()? {
+    while let Some((current_line, line_number, terminator_length)) = input.next_line()? {
         // TODO: Count leading spaces in current line to calculate indentation.
 
-        let mut line = LineCharacters::new(current_line, next_byte_offset);
+        let line_start = next_byte_offset;
+        next_byte_offset += current_line.len() + terminator_length;
+        let mut line = LineCharacters::new(current_line, line_start);
 
         while let Some((code_point, start_byte_offset, remaining_line)) = line.next_char() {
+            macro_rules! token {
+                ($name: ident, $remaining: expr) => {{
+                    let remaining: LineCharacters = $remaining;
+                    let offsets = start_byte_offset..remaining.byte_offset;
+                    locations.insert(line_number, line.column_number, offsets.clone());
+                    tokens.push(Token::$name, Span::new(file, offsets));
+                    line = remaining;
+                    continue;
+                }};
+            }
+
             macro_rules! simple_token {
-                ($name: ident) => {{
-                    // TODO: On token emit, add to the location map.
-                    tokens.push((
-                        Token::$name,
-                        location::OffsetRange {
-                            start: start_byte_offset,
-                            end: start_byte_offset + 1,
-                        },
-                    ));
-                    line = remaining_line;
-                    break;
+                ($name: ident) => {
+                    token!($name, remaining_line)
+                };
+            }
+
+            macro_rules! unexpected_character {
+                ($remaining: expr) => {{
+                    let remaining: LineCharacters = $remaining;
+                    return Err(Error::UnexpectedCharacter {
+                        character: code_point,
+                        span: Span::new(file, start_byte_offset..remaining.byte_offset),
+                    });
                 }};
             }
 
@@ -218,41 +783,76 @@ pub fn tokenize<'o, S: InputSource>(
                 ')' => simple_token!(CloseParenthesis),
                 '[' => simple_token!(OpenSquareBracket),
                 ']' => simple_token!(CloseSquareBracket),
-                '<' => simple_token!(LessThan),
-                '>' => simple_token!(GreaterThan),
+                '<' => match remaining_line.next_char() {
+                    Some(('<', _, after_second)) => match after_second.next_char() {
+                        Some(('<', _, after_third)) => token!(TripleLessThan, after_third),
+                        _ => simple_token!(LessThan),
+                    },
+                    _ => simple_token!(LessThan),
+                },
+                '>' => match remaining_line.next_char() {
+                    Some(('>', _, after_second)) => match after_second.next_char() {
+                        Some(('>', _, after_third)) => token!(TripleGreaterThan, after_third),
+                        _ => simple_token!(GreaterThan),
+                    },
+                    _ => simple_token!(GreaterThan),
+                },
                 '\\' => simple_token!(BackwardSlash),
                 '+' => simple_token!(PlusSign),
                 '-' => simple_token!(MinusSign),
                 '*' => simple_token!(Asterisk),
                 ';' => simple_token!(Semicolon),
                 '/' => simple_token!(ForwardSlash),
-                '.' => simple_token!(Period),
+                '.' => match remaining_line.next_char() {
+                    Some(('.', _, after_periods)) => match after_periods.next_char() {
+                        Some(('=', _, after_equals)) => token!(DoublePeriodEquals, after_equals),
+                        _ => token!(DoublePeriod, after_periods),
+                    },
+                    _ => simple_token!(Period),
+                },
                 '=' => simple_token!(Equals),
-                '&' => simple_token!(Ampersand),
-                '|' => simple_token!(VerticalBar),
+                '&' => match remaining_line.next_char() {
+                    Some(('&', _, after_second)) => match after_second.next_char() {
+                        Some(('&', _, after_third)) => token!(TripleAmpersand, after_third),
+                        _ => token!(DoubleAmpersand, after_second),
+                    },
+                    _ => simple_token!(Ampersand),
+                },
+                '|' => match remaining_line.next_char() {
+                    Some(('|', _, after_second)) => match after_second.next_char() {
+                        Some(('|', _, after_third)) => token!(TripleVerticalBar, after_third),
+                        _ => token!(DoubleVerticalBar, after_second),
+                    },
+                    _ => simple_token!(VerticalBar),
+                },
+                '^' => match remaining_line.next_char() {
+                    Some(('^', _, after_second)) => match after_second.next_char() {
+                        Some(('^', _, after_third)) => token!(TripleCaret, after_third),
+                        _ => unexpected_character!(after_second),
+                    },
+                    _ => unexpected_character!(remaining_line),
+                },
                 //':' // TODO: Check if double colon
-                
-                _ => todo!("other tokens"),
+                _ => unexpected_character!(remaining_line),
             }
         }
-
-        next_byte_offset = line.byte_offset;
     }
 
     Ok(Output {
-        tokens: tokens.clone().into_boxed_slice(),
-        locations: (),
+        tokens: tokens.clone(),
+        locations,
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::location::OffsetRange;
-    use crate::lexer::{self, Token};
+    use crate::lexer::{self, Token, TokenKind};
+    use crate::location::{FileId, Span};
 
     #[test]
     fn token_size_is_acceptable() {
-        assert!(std::mem::size_of::<Token>() <= 16)
+        assert!(core::mem::size_of::<Token>() <= 16);
+        assert_eq!(1, core::mem::size_of::<TokenKind>());
     }
 
     macro_rules! single_token_test {
@@ -260,9 +860,12 @@ mod tests {
             #[test]
             fn $name() {
                 let input: &'static str = $input;
-                let tokens = lexer::tokenize(input, None).unwrap();
+                let tokens = lexer::tokenize(input, FileId::default(), None).unwrap();
                 let expected: Token = $output;
-                assert_eq!(&[ (expected, OffsetRange { start: 0, end: 1 }) ], tokens.tokens())
+                assert_eq!(
+                    vec![(expected, Span::new(FileId::default(), 0..1))],
+                    tokens.tokens().collect::<Vec<_>>()
+                )
             }
         };
     }
@@ -285,4 +888,107 @@ mod tests {
     single_token_test!(equal_sign, "=", Token::Equals);
     single_token_test!(ampersand, "&", Token::Ampersand);
     single_token_test!(vertical_bar, "|", Token::VerticalBar);
+
+    #[test]
+    fn logical_and_bitwise_operators() {
+        let tokens = lexer::tokenize("&&&&&|||||^^^<<<<>>>>", FileId::default(), None).unwrap();
+        assert_eq!(
+            vec![
+                TokenKind::TripleAmpersand,
+                TokenKind::DoubleAmpersand,
+                TokenKind::TripleVerticalBar,
+                TokenKind::DoubleVerticalBar,
+                TokenKind::TripleCaret,
+                TokenKind::TripleLessThan,
+                TokenKind::LessThan,
+                TokenKind::TripleGreaterThan,
+                TokenKind::GreaterThan,
+            ],
+            tokens.kinds()
+        );
+    }
+
+    #[test]
+    fn range_operators() {
+        let tokens = lexer::tokenize("....=..", FileId::default(), None).unwrap();
+        assert_eq!(
+            vec![
+                (Token::DoublePeriod, Span::new(FileId::default(), 0..2)),
+                (
+                    Token::DoublePeriodEquals,
+                    Span::new(FileId::default(), 2..5)
+                ),
+                (Token::DoublePeriod, Span::new(FileId::default(), 5..7)),
+            ],
+            tokens.tokens().collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn carriage_returns_are_counted_in_offsets() {
+        let tokens = lexer::tokenize("(\r\n\r\n..\n)", FileId::default(), None).unwrap();
+        assert_eq!(
+            vec![
+                (Token::OpenParenthesis, Span::new(FileId::default(), 0..1)),
+                (Token::DoublePeriod, Span::new(FileId::default(), 5..7)),
+                (Token::CloseParenthesis, Span::new(FileId::default(), 8..9)),
+            ],
+            tokens.tokens().collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn tokens_are_accessed_by_index() {
+        let tokens = lexer::tokenize("(..", FileId::new(3), None).unwrap();
+        assert_eq!(2, tokens.len());
+
+        let range = tokens.get(1).unwrap();
+        assert_eq!(1, range.index());
+        assert_eq!(TokenKind::DoublePeriod, range.kind());
+        assert_eq!(Span::new(FileId::new(3), 1..3), range.span());
+        assert_eq!(Token::DoublePeriod, range.token());
+        assert!(tokens.get(2).is_none());
+    }
+
+    #[test]
+    fn payloads_are_stored_separately() {
+        let name = crate::identifier::Id::new("value").unwrap().to_identifier();
+        let span = Span::new(FileId::default(), 0..1);
+        let mut buffer = lexer::Buffer::default();
+        buffer.push(Token::OpenParenthesis, span);
+        buffer.push(Token::Identifier(&name), span);
+        buffer.push(Token::LiteralBoolean(true), span);
+
+        assert_eq!(2, buffer.payloads.len());
+        assert_eq!(Some(Token::OpenParenthesis), buffer.token(0));
+        assert_eq!(Some(Token::Identifier(&name)), buffer.token(1));
+        assert_eq!(Some(Token::LiteralBoolean(true)), buffer.token(2));
+        assert_eq!(None, buffer.token(3));
+        assert_eq!(span, buffer.span(2));
+    }
+
+    #[test]
+    fn unexpected_characters_are_errors() {
+        assert_eq!(
+            lexer::Error::UnexpectedCharacter {
+                character: '$',
+                span: Span::new(FileId::default(), 3..4),
+            },
+            lexer::tokenize("(+\n$", FileId::default(), None).unwrap_err()
+        );
+        assert_eq!(
+            lexer::Error::UnexpectedCharacter {
+                character: '^',
+                span: Span::new(FileId::default(), 0..1),
+            },
+            lexer::tokenize("^", FileId::default(), None).unwrap_err()
+        );
+        assert_eq!(
+            lexer::Error::UnexpectedCharacter {
+                character: '^',
+                span: Span::new(FileId::default(), 4..6),
+            },
+            lexer::tokenize("^^^(^^", FileId::default(), None).unwrap_err()
+        );
+    }
 }
@@ -1,8 +1,42 @@
 //! F#-like language with some features borrowed from Rust
+//!
+//! Without the default `std` feature, the crate only depends on `core` and `alloc`. The modules that read files or
+//! use threads, such as `project` and `vfs`, are only available with the `std` feature.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 pub mod ast;
+pub mod cst;
+pub mod diagnostics;
+pub mod escape;
+pub mod float;
+#[cfg(feature = "std")]
+pub mod ice;
 pub mod identifier;
+pub mod layout;
 pub mod lexer;
+pub mod limits;
 pub mod location;
+#[cfg(feature = "stats")]
+pub mod memory;
+pub mod parser;
+pub mod path;
+mod prelude;
 pub mod print;
+#[cfg(feature = "std")]
+pub mod profile;
+#[cfg(feature = "std")]
+pub mod project;
+pub mod reduce;
+pub mod rewrite;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod target;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod text;
 pub mod types;
+#[cfg(feature = "std")]
+pub mod vfs;
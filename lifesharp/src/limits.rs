@@ -0,0 +1,210 @@
+//! Limits on the size and complexity of source code, which protect the compiler from running out of stack space or
+//! memory when given generated or minified input.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::ast::{Expression, TopDeclaration, Tree};
+use crate::diagnostics::{Diagnostic, DiagnosticSink, Severity};
+use crate::location::{FileId, Span};
+use crate::prelude::*;
+
+/// The code of the diagnostic reported when a line is longer than [`Limits::max_line_length`].
+pub const LINE_TOO_LONG: &str = "E0001";
+
+/// The code of the diagnostic reported when a line is indented more than [`Limits::max_indentation_depth`] times.
+pub const INDENTATION_TOO_DEEP: &str = "E0002";
+
+/// The code of the diagnostic reported when expressions are nested more than [`Limits::max_nesting_depth`] times.
+pub const NESTING_TOO_DEEP: &str = "E0003";
+
+/// Specifies how large and complex source code is allowed to be.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    any(feature = "std", feature = "serialize"),
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case")
+)]
+#[non_exhaustive]
+pub struct Limits {
+    /// The maximum length of a line, in bytes.
+    pub max_line_length: usize,
+    /// The maximum number of times that lines can be indented relative to each other.
+    pub max_indentation_depth: usize,
+    /// The maximum number of expressions that can be nested inside each other.
+    pub max_nesting_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_line_length: 10_000,
+            max_indentation_depth: 64,
+            max_nesting_depth: 256,
+        }
+    }
+}
+
+impl Limits {
+    /// Reports lines in the source code that are too long or indented too deeply.
+    ///
+    /// A line is indented one level deeper than the closest previous line with less indentation, so the depth does not
+    /// depend on the number of spaces used for each level.
+    pub fn check_lines(&self, source: &str, file: FileId, sink: &dyn DiagnosticSink) {
+        let mut indentation_stack = Vec::<usize>::new();
+        let mut offset = 0;
+
+        for line in source.split_inclusive('\n') {
+            let text = line.trim_end_matches(['\n', '\r']);
+
+            if text.len() > self.max_line_length {
+                sink.emit(Diagnostic::new(
+                    Severity::Error,
+                    LINE_TOO_LONG,
+                    format!(
+                        "line is {} bytes long, which exceeds the limit of {}",
+                        text.len(),
+                        self.max_line_length
+                    ),
+                    Span::new(file, offset..offset + text.len()),
+                ));
+            }
+
+            let content = text.trim_start_matches(' ');
+            if !content.is_empty() {
+                let indentation = text.len() - content.len();
+                while indentation_stack
+                    .last()
+                    .is_some_and(|previous| *previous >= indentation)
+                {
+                    indentation_stack.pop();
+                }
+
+                if indentation > 0 {
+                    indentation_stack.push(indentation);
+                }
+
+                if indentation_stack.len() > self.max_indentation_depth {
+                    sink.emit(Diagnostic::new(
+                        Severity::Error,
+                        INDENTATION_TOO_DEEP,
+                        format!(
+                            "line is indented {} levels deep, which exceeds the limit of {}",
+                            indentation_stack.len(),
+                            self.max_indentation_depth
+                        ),
+                        Span::new(file, offset..offset + indentation),
+                    ));
+                }
+            }
+
+            offset += line.len();
+        }
+    }
+
+    /// Reports expressions in the tree that are nested too deeply.
+    ///
+    /// Each expression whose operands are nested too deeply is reported once, and its operands are not checked further.
+    /// The tree is traversed without recursion, so trees of any depth can be checked.
+    pub fn check_nesting(&self, tree: &Tree<'_>, sink: &dyn DiagnosticSink) {
+        let mut stack = Vec::<(&Expression<'_>, usize, Span)>::new();
+
+        for declaration in tree.declarations.iter() {
+            match declaration {
+                TopDeclaration::FunctionDefinition(definition) => {
+                    for expression in definition.body.iter() {
+                        stack.push((&expression.content, 1, expression.location));
+                    }
+                }
+            }
+        }
+
+        while let Some((expression, depth, span)) = stack.pop() {
+            let nested_start = stack.len();
+            let depth = depth + 1;
+
+            // Nested expressions do not store their own location, so they are reported at their operator if they have one,
+            // and at the closest enclosing location otherwise.
+            let span = match expression {
+                Expression::BinaryOperation(operation) => operation.operator.location,
+                Expression::UnaryOperation(operation) => operation.operator.location,
+                Expression::Parenthesized(inner) => inner.location,
+                _ => span,
+            };
+
+            for child in expression.children() {
+                stack.push((child, depth, span));
+            }
+
+            if depth > self.max_nesting_depth && stack.len() > nested_start {
+                stack.truncate(nested_start);
+                sink.emit(Diagnostic::new(
+                    Severity::Error,
+                    NESTING_TOO_DEEP,
+                    format!(
+                        "expressions are nested more than {} levels deep",
+                        self.max_nesting_depth
+                    ),
+                    span,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::ast::{
+        BinaryOperation, BinaryOperator, Expression, FunctionDefinition, Located, Tree,
+    };
+    use crate::diagnostics::SortedSink;
+    use crate::identifier::Id;
+    use crate::limits::{self, Limits};
+    use crate::location::FileId;
+
+    fn codes(sink: SortedSink) -> Vec<&'static str> {
+        sink.finish()
+            .into_iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect()
+    }
+
+    #[test]
+    fn long_and_deeply_indented_lines_are_reported() {
+        let limits = Limits {
+            max_line_length: 12,
+            max_indentation_depth: 2,
+            ..Limits::default()
+        };
+
+        let sink = SortedSink::default();
+        let source = "a\n  b\n    c\n  d\n   e\n      f\n\nthis line is long\n";
+        limits.check_lines(source, FileId::default(), &sink);
+
+        assert_eq!(
+            vec![limits::INDENTATION_TOO_DEEP, limits::LINE_TOO_LONG],
+            codes(sink)
+        );
+    }
+
+    #[test]
+    fn deeply_nested_expressions_are_reported_without_overflowing() {
+        let mut expression = Expression::BooleanLiteral(true);
+        for _ in 0..10_000 {
+            expression = Expression::BinaryOperation(Box::new(BinaryOperation::new(
+                expression,
+                Located::dummy(BinaryOperator::Add),
+                Expression::BooleanLiteral(false),
+            )));
+        }
+
+        let mut definition = FunctionDefinition::new(Located::dummy(Id::new("f").unwrap()));
+        definition.body.push(Located::dummy(expression));
+        let tree = Tree {
+            declarations: vec![definition.into()],
+        };
+
+        let sink = SortedSink::default();
+        Limits::default().check_nesting(&tree, &sink);
+        assert_eq!(vec![limits::NESTING_TOO_DEEP], codes(sink));
+    }
+}
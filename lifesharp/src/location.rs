@@ -2,13 +2,18 @@
 
 #![deny(missing_docs, missing_debug_implementations)]
 
-use std::collections::btree_map;
+use crate::prelude::*;
+use alloc::collections::btree_map;
+use core::ops::Range;
 
 /// Represents a line or column number.
-pub use std::num::NonZeroUsize as Number;
+pub use core::num::NonZeroUsize as Number;
 
 /// The first line or column number.
-pub const FIRST_NUMBER: Number = unsafe { Number::new_unchecked(1) };
+pub const FIRST_NUMBER: Number = match Number::new(1) {
+    Some(number) => number,
+    None => unreachable!(),
+};
 
 /// Increments a line or column number.
 ///
@@ -21,11 +26,149 @@ pub(crate) fn increment_number(number: &mut Number) {
 /// Represents a UTF-8 byte offset into a source code file.
 pub type Offset = usize;
 
-/// Represents a range of characters in a source code file.
-pub type OffsetRange = std::ops::Range<Offset>;
+/// Identifies a source code file.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(transparent))]
+pub struct FileId(u32);
+
+impl FileId {
+    /// Creates an identifier for the source file with the specified index.
+    pub const fn new(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// Gets the index of the source file.
+    pub const fn index(self) -> u32 {
+        self.0
+    }
+}
+
+/// Represents a range of bytes in a source code file.
+///
+/// Spans are ordered by their file, then by their start offset, then by their end offset, so sorting spans puts them
+/// in the order that they appear in each file.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    file: FileId,
+    start: u32,
+    len: u32,
+}
+
+fn compact_offset(offset: Offset) -> u32 {
+    u32::try_from(offset).expect("source files larger than 4 GiB are not supported")
+}
+
+impl Span {
+    /// A span that does not correspond to any characters in a source code file, used for content that was generated by
+    /// the compiler rather than written by the user.
+    pub const SYNTHETIC: Self = Self {
+        file: FileId(u32::MAX),
+        start: u32::MAX,
+        len: 0,
+    };
+
+    /// Creates a span covering the specified range of bytes in a source file.
+    ///
+    /// # Panics
+    /// Panics if the range is reversed or if its end does not fit in 32 bits.
+    pub fn new(file: FileId, range: Range<Offset>) -> Self {
+        let start = compact_offset(range.start);
+        let end = compact_offset(range.end);
+        Self {
+            file,
+            start,
+            len: end.checked_sub(start).expect("span range is reversed"),
+        }
+    }
+
+    /// Gets the source file that the span is in.
+    pub fn file(self) -> FileId {
+        self.file
+    }
+
+    /// Gets the offset of the first byte in the span.
+    pub fn start(self) -> Offset {
+        self.start as Offset
+    }
+
+    /// Gets the offset just after the last byte in the span.
+    pub fn end(self) -> Offset {
+        self.start() + self.len()
+    }
+
+    /// Gets the length of the span, in bytes.
+    pub fn len(self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns `true` if the span does not contain any bytes.
+    pub fn is_empty(self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the range of bytes covered by the span.
+    pub fn range(self) -> Range<Offset> {
+        self.start()..self.end()
+    }
+
+    /// Returns `true` if the span marks generated content, which diagnostics should avoid pointing to.
+    pub fn is_synthetic(self) -> bool {
+        self == Self::SYNTHETIC
+    }
+
+    /// Returns `true` if the offset is within the span.
+    pub fn contains(self, offset: Offset) -> bool {
+        !self.is_synthetic() && self.range().contains(&offset)
+    }
+
+    /// Gets the smallest span that covers both spans.
+    ///
+    /// If one of the spans is [`Span::SYNTHETIC`], the other span is returned. Spans in different files cannot be
+    /// merged, so this span is returned in that case.
+    pub fn merge(self, other: Span) -> Span {
+        if self.is_synthetic() {
+            other
+        } else if other.is_synthetic() || self.file != other.file {
+            self
+        } else {
+            Span::new(
+                self.file,
+                self.start().min(other.start())..self.end().max(other.end()),
+            )
+        }
+    }
+
+    /// Gets the span covered by both spans, or `None` if the spans do not overlap.
+    ///
+    /// Spans that touch without overlapping intersect at an empty span. Synthetic spans and spans in different files
+    /// never intersect.
+    pub fn intersection(self, other: Span) -> Option<Span> {
+        if self.is_synthetic() || other.is_synthetic() || self.file != other.file {
+            return None;
+        }
+
+        let start = self.start().max(other.start());
+        let end = self.end().min(other.end());
+        if start <= end {
+            Some(Span::new(self.file, start..end))
+        } else {
+            None
+        }
+    }
+}
+
+/// Gets the smallest span that covers both spans, see [`Span::merge`].
+pub fn span_merge(a: Span, b: Span) -> Span {
+    a.merge(b)
+}
 
 /// Represents a line and column number in a source file.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// Locations are ordered by their line number, then by their column number.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Location {
     line: Number,
     column: Number,
@@ -49,22 +192,372 @@ impl Location {
     }
 }
 
-#[derive(Clone, Debug)]
-struct MapKey(OffsetRange);
+/// A zero-based line number and column in UTF-16 code units, as used by the Language Server Protocol.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct LspPosition {
+    /// The line number, starting at zero.
+    pub line: u32,
+    /// The number of UTF-16 code units from the start of the line.
+    pub character: u32,
+}
+
+impl LspPosition {
+    /// Creates a position from a zero-based line number and a column in UTF-16 code units.
+    pub fn new(line: u32, character: u32) -> Self {
+        Self { line, character }
+    }
+}
 
 #[derive(Clone, Debug)]
 struct MapEntry {
-    line: Number,
+    end: Offset,
+    location: Location,
 }
 
 /// Maps offsets in a source file to line and column numbers.
 #[derive(Clone, Debug, Default)]
 pub struct Map {
-    lookup: btree_map::BTreeMap<MapKey, MapEntry>,
+    /// The ranges in the source file, keyed by their start offset.
+    lookup: btree_map::BTreeMap<Offset, MapEntry>,
 }
 
 impl Map {
-    pub(crate) fn insert(&mut self, line: Number, column: Number, offset_range: OffsetRange) {
-        todo!("insert location")
+    pub(crate) fn insert(&mut self, line: Number, column: Number, offset_range: Range<Offset>) {
+        self.lookup.insert(
+            offset_range.start,
+            MapEntry {
+                end: offset_range.end,
+                location: Location { line, column },
+            },
+        );
+    }
+
+    /// Gets the line and column number of the start of the range that contains the specified offset.
+    pub fn get(&self, offset: Offset) -> Option<&Location> {
+        let (start, entry) = self.lookup.range(..=offset).next_back()?;
+        if offset == *start || offset < entry.end {
+            Some(&entry.location)
+        } else {
+            None
+        }
+    }
+
+    /// Removes all ranges from the map.
+    pub fn clear(&mut self) {
+        self.lookup.clear()
+    }
+}
+
+#[cfg(feature = "stats")]
+impl crate::memory::MemoryUsage for Map {
+    fn memory_usage(&self, report: &mut crate::memory::Report) {
+        report.record(
+            "locations",
+            self.lookup.len(),
+            self.lookup.len() * core::mem::size_of::<(Offset, MapEntry)>(),
+        )
+    }
+}
+
+/// The text of a source file registered in a [`SourceMap`], along with the offsets of the start of each line.
+#[derive(Clone, Debug)]
+struct SourceText {
+    name: String,
+    text: String,
+    line_starts: Vec<Offset>,
+}
+
+/// Stores the text of source files, allowing the spans in them to be converted to line and column numbers.
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceText>,
+    path_remappings: Vec<(String, String)>,
+}
+
+impl SourceMap {
+    /// Replaces the prefix of the names of source files added later, so that absolute paths on the machine that
+    /// compiled the code are not embedded in diagnostics or compiled output.
+    ///
+    /// If the prefixes of multiple remappings match a name, the remapping added last is used.
+    pub fn remap_path_prefix(&mut self, from: impl Into<String>, to: impl Into<String>) {
+        self.path_remappings.push((from.into(), to.into()));
+    }
+
+    /// Registers a source file, returning the identifier used in the spans of its tokens.
+    ///
+    /// The name of the file is changed according to the path remappings.
+    pub fn add(&mut self, name: impl Into<String>, text: impl Into<String>) -> FileId {
+        let mut name = name.into();
+        if let Some((rest, to)) = self
+            .path_remappings
+            .iter()
+            .rev()
+            .find_map(|(from, to)| Some((name.strip_prefix(from.as_str())?, to)))
+        {
+            name = format!("{to}{rest}");
+        }
+
+        let text = text.into();
+        let line_starts = core::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+
+        let id = FileId(u32::try_from(self.files.len()).expect("too many source files"));
+        self.files.push(SourceText {
+            name,
+            text,
+            line_starts,
+        });
+        id
+    }
+
+    /// Gets the name of a source file.
+    pub fn name(&self, file: FileId) -> Option<&str> {
+        Some(&self.files.get(file.0 as usize)?.name)
+    }
+
+    /// Gets the text of a source file.
+    pub fn text(&self, file: FileId) -> Option<&str> {
+        Some(&self.files.get(file.0 as usize)?.text)
+    }
+
+    /// Gets the line and column number of an offset in a source file.
+    ///
+    /// Returns `None` if the file is not in the map, or if the offset is not on a character boundary.
+    pub fn location(&self, file: FileId, offset: Offset) -> Option<Location> {
+        let source = self.files.get(file.0 as usize)?;
+        let line = source.line_starts.partition_point(|start| *start <= offset) - 1;
+        let line_start = source.line_starts[line];
+        let column = source.text.get(line_start..offset)?.chars().count();
+
+        Some(Location {
+            line: Number::new(line + 1)?,
+            column: Number::new(column + 1)?,
+        })
+    }
+
+    /// Gets the text of a line without its line terminator, along with the offset of its start.
+    fn line_text(source: &SourceText, line: usize) -> Option<(Offset, &str)> {
+        let start = *source.line_starts.get(line)?;
+        let end = source
+            .line_starts
+            .get(line + 1)
+            .map_or(source.text.len(), |next| *next - 1);
+        let text = &source.text[start..end];
+        Some((start, text.strip_suffix('\r').unwrap_or(text)))
+    }
+
+    /// Converts an offset in a source file to a position used by the Language Server Protocol, whose columns are
+    /// measured in UTF-16 code units rather than code points.
+    ///
+    /// Returns `None` if the file is not in the map, or if the offset is not on a character boundary.
+    pub fn to_lsp_position(&self, file: FileId, offset: Offset) -> Option<LspPosition> {
+        let source = self.files.get(file.0 as usize)?;
+        let line = source.line_starts.partition_point(|start| *start <= offset) - 1;
+        let line_start = source.line_starts[line];
+        let character = source
+            .text
+            .get(line_start..offset)?
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+
+        Some(LspPosition {
+            line: u32::try_from(line).ok()?,
+            character: u32::try_from(character).ok()?,
+        })
+    }
+
+    /// Converts a position used by the Language Server Protocol to an offset in a source file.
+    ///
+    /// As required by the protocol, a column past the end of the line refers to the end of the line. A column in the
+    /// middle of a surrogate pair refers to the start of the character that the pair encodes. Returns `None` if the
+    /// file is not in the map, or if the line does not exist.
+    pub fn from_lsp_position(&self, file: FileId, position: LspPosition) -> Option<Offset> {
+        let source = self.files.get(file.0 as usize)?;
+        let (line_start, text) = Self::line_text(source, position.line as usize)?;
+        let mut remaining = position.character as usize;
+
+        for (offset, c) in text.char_indices() {
+            if remaining < c.len_utf16() {
+                return Some(line_start + offset);
+            }
+
+            remaining -= c.len_utf16();
+        }
+
+        Some(line_start + text.len())
+    }
+
+    /// Gets the locations of the start and end of a span, or `None` if the span is [`Span::SYNTHETIC`].
+    pub fn span_locations(&self, span: Span) -> Option<(Location, Location)> {
+        if span.is_synthetic() {
+            return None;
+        }
+
+        Some((
+            self.location(span.file, span.start())?,
+            self.location(span.file, span.end())?,
+        ))
+    }
+}
+
+#[cfg(feature = "stats")]
+impl crate::memory::MemoryUsage for SourceMap {
+    fn memory_usage(&self, report: &mut crate::memory::Report) {
+        report.record_vec("source_map.files", &self.files);
+        for file in self.files.iter() {
+            report.record(
+                "source_map.text",
+                1,
+                file.name.capacity() + file.text.capacity(),
+            );
+            report.record_vec("source_map.line_starts", &file.line_starts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::location::{self, FileId, LspPosition, SourceMap, Span};
+
+    fn span(start: usize, end: usize) -> Span {
+        Span::new(FileId::default(), start..end)
+    }
+
+    #[test]
+    fn merged_span_covers_both_spans() {
+        assert_eq!(span(2, 9), location::span_merge(span(5, 9), span(2, 4)));
+        assert_eq!(
+            span(2, 4),
+            location::span_merge(Span::SYNTHETIC, span(2, 4))
+        );
+        assert_eq!(
+            span(5, 9),
+            location::span_merge(span(5, 9), Span::SYNTHETIC)
+        );
+    }
+
+    #[test]
+    fn spans_in_different_files_are_not_merged() {
+        let other = Span::new(FileId::new(1), 0..1);
+        assert_eq!(span(5, 9), location::span_merge(span(5, 9), other));
+    }
+
+    #[test]
+    fn intersection_is_covered_by_both_spans() {
+        assert_eq!(Some(span(5, 7)), span(2, 7).intersection(span(5, 9)));
+        assert_eq!(Some(span(4, 4)), span(2, 4).intersection(span(4, 9)));
+        assert_eq!(None, span(2, 4).intersection(span(5, 9)));
+        assert_eq!(None, span(2, 4).intersection(Span::SYNTHETIC));
+        assert_eq!(
+            None,
+            span(2, 4).intersection(Span::new(FileId::new(1), 2..4))
+        );
+    }
+
+    #[test]
+    fn spans_and_locations_are_sorted_by_position() {
+        let mut spans = vec![
+            Span::new(FileId::new(1), 0..1),
+            span(4, 6),
+            span(2, 9),
+            span(2, 3),
+        ];
+        spans.sort();
+        assert_eq!(
+            vec![
+                span(2, 3),
+                span(2, 9),
+                span(4, 6),
+                Span::new(FileId::new(1), 0..1)
+            ],
+            spans
+        );
+
+        let mut map = SourceMap::default();
+        let file = map.add("test.lfs", "abc\nd\n");
+        assert!(map.location(file, 2) < map.location(file, 4));
+        assert!(map.location(file, 0) < map.location(file, 2));
+    }
+
+    #[cfg(all(feature = "serialize", feature = "std"))]
+    #[test]
+    fn spans_are_serialized() {
+        let original = Span::new(FileId::new(3), 5..9);
+        let serialized = toml::to_string(&original).unwrap();
+        assert_eq!(original, toml::from_str::<Span>(&serialized).unwrap());
+    }
+
+    #[test]
+    fn path_prefixes_are_remapped() {
+        let mut map = SourceMap::default();
+        map.remap_path_prefix("/home/user/", "");
+        map.remap_path_prefix("/home/user/deps/", "deps:");
+        let main = map.add("/home/user/src/main.lfs", "");
+        let dependency = map.add("/home/user/deps/util.lfs", "");
+        let other = map.add("/tmp/other.lfs", "");
+
+        assert_eq!(Some("src/main.lfs"), map.name(main));
+        assert_eq!(Some("deps:util.lfs"), map.name(dependency));
+        assert_eq!(Some("/tmp/other.lfs"), map.name(other));
+    }
+
+    #[test]
+    fn synthetic_span_contains_nothing() {
+        assert!(span(2, 4).contains(3));
+        assert!(!span(2, 4).contains(4));
+        assert!(!Span::SYNTHETIC.contains(u32::MAX as usize));
+    }
+
+    #[test]
+    fn span_size_is_acceptable() {
+        assert_eq!(12, core::mem::size_of::<Span>());
+    }
+
+    #[test]
+    fn offsets_are_converted_to_line_and_column_numbers() {
+        let mut map = SourceMap::default();
+        let file = map.add("test.lfs", "ab\n\u{e9}c\n");
+        let location = map.location(file, 5).unwrap();
+        assert_eq!(
+            (2, 2),
+            (location.line_number().get(), location.column_number().get())
+        );
+        let location = map.location(file, 7).unwrap();
+        assert_eq!(
+            (3, 1),
+            (location.line_number().get(), location.column_number().get())
+        );
+        assert!(map.location(file, 4).is_none());
+        assert!(map.span_locations(Span::SYNTHETIC).is_none());
+    }
+
+    #[test]
+    fn lsp_positions_count_utf16_code_units() {
+        let mut map = SourceMap::default();
+        let file = map.add("test.lfs", "a\u{e9}\u{1F600}b\r\nc\n");
+
+        assert_eq!(Some(LspPosition::new(0, 2)), map.to_lsp_position(file, 3));
+        assert_eq!(Some(LspPosition::new(0, 4)), map.to_lsp_position(file, 7));
+        assert_eq!(Some(LspPosition::new(1, 0)), map.to_lsp_position(file, 10));
+        assert_eq!(None, map.to_lsp_position(file, 4));
+
+        assert_eq!(Some(7), map.from_lsp_position(file, LspPosition::new(0, 4)));
+        assert_eq!(Some(3), map.from_lsp_position(file, LspPosition::new(0, 3)));
+        assert_eq!(
+            Some(8),
+            map.from_lsp_position(file, LspPosition::new(0, 99))
+        );
+        assert_eq!(
+            Some(11),
+            map.from_lsp_position(file, LspPosition::new(1, 1))
+        );
+        assert_eq!(
+            Some(12),
+            map.from_lsp_position(file, LspPosition::new(2, 0))
+        );
+        assert_eq!(None, map.from_lsp_position(file, LspPosition::new(3, 0)));
     }
 }
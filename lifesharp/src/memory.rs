@@ -0,0 +1,103 @@
+//! Measures the memory used by the data structures of the compiler, enabled by the `stats` feature.
+//!
+//! Sizes are estimates based on the capacity of the buffers owned by each structure, and do not include overhead added
+//! by the allocator.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+/// The memory used by one kind of object, such as the token kinds stored by the lexer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Entry {
+    /// Describes what is being measured, such as `lexer.kinds`.
+    pub name: &'static str,
+    /// The number of objects.
+    pub count: usize,
+    /// The estimated number of bytes used by the objects.
+    pub bytes: usize,
+}
+
+/// Collects the memory used by several data structures.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    entries: Vec<Entry>,
+}
+
+impl Report {
+    /// Records the memory used by some objects, adding to any previous entry with the same name.
+    pub fn record(&mut self, name: &'static str, count: usize, bytes: usize) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.name == name) {
+            entry.count += count;
+            entry.bytes += bytes;
+        } else {
+            self.entries.push(Entry { name, count, bytes });
+        }
+    }
+
+    /// Records the memory used by the elements of a vector, including any unused capacity.
+    pub fn record_vec<T>(&mut self, name: &'static str, elements: &Vec<T>) {
+        self.record(
+            name,
+            elements.len(),
+            elements.capacity() * std::mem::size_of::<T>(),
+        )
+    }
+
+    /// Gets the entries in the order that they were first recorded in.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Gets the total number of bytes used by all entries.
+    pub fn total_bytes(&self) -> usize {
+        self.entries.iter().map(|entry| entry.bytes).sum()
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in self.entries.iter() {
+            writeln!(
+                f,
+                "{:<24} {:>10} {:>12} B",
+                entry.name, entry.count, entry.bytes
+            )?;
+        }
+
+        write!(f, "{:<24} {:>10} {:>12} B", "total", "", self.total_bytes())
+    }
+}
+
+/// Implemented by data structures that can report how much memory they use.
+pub trait MemoryUsage {
+    /// Adds entries describing the memory used by `self` to the `report`.
+    fn memory_usage(&self, report: &mut Report);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::location::SourceMap;
+    use crate::memory::{MemoryUsage, Report};
+
+    #[test]
+    fn entries_with_same_name_are_combined() {
+        let mut source_map = SourceMap::default();
+        source_map.add("a.lfs", "a\nb\n");
+        source_map.add("b.lfs", "c");
+
+        let mut report = Report::default();
+        source_map.memory_usage(&mut report);
+        source_map.memory_usage(&mut report);
+
+        let text = report
+            .entries()
+            .iter()
+            .find(|entry| entry.name == "source_map.text")
+            .unwrap();
+
+        assert_eq!(4, text.count);
+        // Each file is counted twice, and strings may allocate more than the length of their text.
+        assert!(text.bytes >= 30);
+        assert!(report.total_bytes() > text.bytes);
+    }
+}
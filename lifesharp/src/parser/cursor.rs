@@ -0,0 +1,109 @@
+use crate::lexer::{self, TokenKind, TokenRef};
+
+/// A position that a [`Cursor`] can return to, used when speculatively parsing a grammar rule.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Checkpoint(usize);
+
+/// Moves through the tokens of a source file, allowing the parser to look ahead and backtrack.
+#[derive(Clone, Debug)]
+pub struct Cursor<'a, 'o> {
+    tokens: &'a lexer::Output<'o>,
+    position: usize,
+}
+
+impl<'a, 'o> Cursor<'a, 'o> {
+    /// Creates a cursor starting at the first token.
+    pub fn new(tokens: &'a lexer::Output<'o>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    /// Gets the index of the next token.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns `true` if all of the tokens have been consumed.
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    /// Gets the token `n` tokens ahead of the cursor without consuming it, where `peek(0)` is the next token.
+    pub fn peek(&self, n: usize) -> Option<TokenRef<'a, 'o>> {
+        self.tokens.get(self.position.checked_add(n)?)
+    }
+
+    /// Gets the kind of the token `n` tokens ahead of the cursor.
+    pub fn peek_kind(&self, n: usize) -> Option<TokenKind> {
+        self.peek(n).map(TokenRef::kind)
+    }
+
+    /// Returns `true` if the next token is of the specified kind.
+    pub fn at(&self, kind: TokenKind) -> bool {
+        self.peek_kind(0) == Some(kind)
+    }
+
+    /// Consumes the next token.
+    pub fn bump(&mut self) -> Option<TokenRef<'a, 'o>> {
+        let token = self.peek(0)?;
+        self.position += 1;
+        Some(token)
+    }
+
+    /// Consumes the next token only if it is of the specified kind.
+    pub fn eat(&mut self, kind: TokenKind) -> Option<TokenRef<'a, 'o>> {
+        if self.at(kind) {
+            self.bump()
+        } else {
+            None
+        }
+    }
+
+    /// Records the current position of the cursor.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.position)
+    }
+
+    /// Returns the cursor to a previously recorded position, so that the tokens after it can be parsed again.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.position = checkpoint.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::{self, TokenKind};
+    use crate::location::FileId;
+    use crate::parser::Cursor;
+
+    #[test]
+    fn peek_does_not_consume_tokens() {
+        let tokens = lexer::tokenize("(+)", FileId::default(), None).unwrap();
+        let mut cursor = Cursor::new(&tokens);
+        assert_eq!(Some(TokenKind::CloseParenthesis), cursor.peek_kind(2));
+        assert_eq!(None, cursor.peek_kind(3));
+        assert!(cursor.eat(TokenKind::PlusSign).is_none());
+        assert_eq!(0, cursor.bump().unwrap().index());
+        assert!(cursor.eat(TokenKind::PlusSign).is_some());
+        assert!(cursor.at(TokenKind::CloseParenthesis));
+    }
+
+    #[test]
+    fn rollback_returns_to_checkpoint() {
+        let tokens = lexer::tokenize("<+>", FileId::default(), None).unwrap();
+        let mut cursor = Cursor::new(&tokens);
+        let checkpoint = cursor.checkpoint();
+        cursor.bump();
+        cursor.bump();
+        assert!(cursor.eat(TokenKind::Period).is_none());
+        cursor.rollback(checkpoint);
+        assert!(cursor.at(TokenKind::LessThan));
+        cursor.bump();
+        cursor.bump();
+        cursor.bump();
+        assert!(cursor.is_at_end());
+        assert!(cursor.bump().is_none());
+    }
+}
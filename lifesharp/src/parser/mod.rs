@@ -0,0 +1,7 @@
+//! Parsing of LifeSharp source code.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+mod cursor;
+
+pub use cursor::{Checkpoint, Cursor};
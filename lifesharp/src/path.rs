@@ -0,0 +1,240 @@
+//! Interning of the paths used to refer to definitions, so that each distinct path is only stored once.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::ast::{self, Located, PathId};
+use crate::identifier::{Id, Identifier};
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+
+/// Refers to a path stored in a [`PathInterner`].
+///
+/// Handles returned by the same interner are equal only if the paths that they refer to are equal, making comparison
+/// and hashing of paths cheap.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PathHandle(u32);
+
+impl PathHandle {
+    /// The empty relative path, which refers to definitions in the current scope.
+    pub const RELATIVE: Self = Self(0);
+
+    /// The empty global path, which refers to the root of the package.
+    pub const GLOBAL: Self = Self(1);
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Root,
+    Segment {
+        parent: PathHandle,
+        /// Index of the segment name in [`PathInterner::names`].
+        name: u32,
+        /// The number of segments in the path, including this one.
+        len: u32,
+    },
+}
+
+/// Stores paths as a tree of segments, where each path shares its storage with every path that it is a prefix of.
+#[derive(Clone, Debug)]
+pub struct PathInterner {
+    names: Vec<Identifier>,
+    /// Indices into [`PathInterner::names`], sorted by the names that they refer to, so that each name is only stored
+    /// once.
+    name_lookup: Vec<u32>,
+    nodes: Vec<Node>,
+    node_lookup: BTreeMap<(PathHandle, u32), PathHandle>,
+}
+
+impl Default for PathInterner {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            name_lookup: Vec::new(),
+            nodes: vec![Node::Root, Node::Root],
+            node_lookup: BTreeMap::new(),
+        }
+    }
+}
+
+impl PathInterner {
+    fn node(&self, path: PathHandle) -> &Node {
+        self.nodes
+            .get(path.0 as usize)
+            .expect("path should belong to this interner")
+    }
+
+    /// Gets the path formed by appending a segment to the end of another path.
+    pub fn child(&mut self, parent: PathHandle, segment: &Id) -> PathHandle {
+        let lookup = self
+            .name_lookup
+            .binary_search_by(|name| self.names[*name as usize].as_id().cmp(segment));
+
+        let name = match lookup {
+            Ok(index) => self.name_lookup[index],
+            Err(index) => {
+                let name = u32::try_from(self.names.len()).expect("too many path segments");
+                self.names.push(segment.to_identifier());
+                self.name_lookup.insert(index, name);
+                name
+            }
+        };
+
+        if let Some(existing) = self.node_lookup.get(&(parent, name)) {
+            return *existing;
+        }
+
+        let len = self.len(parent) as u32 + 1;
+        let handle = PathHandle(u32::try_from(self.nodes.len()).expect("too many paths"));
+        self.nodes.push(Node::Segment { parent, name, len });
+        self.node_lookup.insert((parent, name), handle);
+        handle
+    }
+
+    /// Interns a path from the syntax tree.
+    pub fn intern(&mut self, path: &PathId<'_>) -> PathHandle {
+        let root = if path.global {
+            PathHandle::GLOBAL
+        } else {
+            PathHandle::RELATIVE
+        };
+
+        path.identifiers
+            .iter()
+            .fold(root, |parent, segment| self.child(parent, segment.content))
+    }
+
+    /// Gets the path without its last segment, or `None` if the path is empty.
+    pub fn parent(&self, path: PathHandle) -> Option<PathHandle> {
+        match self.node(path) {
+            Node::Root => None,
+            Node::Segment { parent, .. } => Some(*parent),
+        }
+    }
+
+    /// Gets the last segment of the path, or `None` if the path is empty.
+    pub fn last(&self, path: PathHandle) -> Option<&Id> {
+        match self.node(path) {
+            Node::Root => None,
+            Node::Segment { name, .. } => Some(self.names[*name as usize].as_id()),
+        }
+    }
+
+    /// Gets the number of segments in the path.
+    pub fn len(&self, path: PathHandle) -> usize {
+        match self.node(path) {
+            Node::Root => 0,
+            Node::Segment { len, .. } => *len as usize,
+        }
+    }
+
+    /// Returns `true` if the path starts at the root of the package.
+    pub fn is_global(&self, mut path: PathHandle) -> bool {
+        while let Some(parent) = self.parent(path) {
+            path = parent;
+        }
+
+        path == PathHandle::GLOBAL
+    }
+
+    /// Gets the segments of the path, starting with the first.
+    pub fn segments(&self, mut path: PathHandle) -> Vec<&Id> {
+        let mut segments = Vec::with_capacity(self.len(path));
+        while let Some(segment) = self.last(path) {
+            segments.push(segment);
+            path = self
+                .parent(path)
+                .expect("non-empty path should have a parent");
+        }
+
+        segments.reverse();
+        segments
+    }
+
+    /// Reconstructs a path that can be printed, with synthetic locations for each segment.
+    pub fn to_path_id(&self, path: PathHandle) -> PathId<'_> {
+        let identifiers = self
+            .segments(path)
+            .into_iter()
+            .map(Located::dummy)
+            .collect::<Vec<ast::Id<'_>>>();
+
+        if self.is_global(path) {
+            PathId::global(identifiers)
+        } else {
+            PathId::relative(identifiers)
+        }
+    }
+}
+
+#[cfg(feature = "stats")]
+impl crate::memory::MemoryUsage for PathInterner {
+    fn memory_usage(&self, report: &mut crate::memory::Report) {
+        report.record_vec("paths.nodes", &self.nodes);
+        report.record_vec("paths.names", &self.names);
+        for name in self.names.iter() {
+            report.record("paths.name_text", 1, name.capacity());
+        }
+        report.record_vec("paths.name_lookup", &self.name_lookup);
+        report.record(
+            "paths.node_lookup",
+            self.node_lookup.len(),
+            self.node_lookup.len() * core::mem::size_of::<((PathHandle, u32), PathHandle)>(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{Located, PathId};
+    use crate::identifier::Id;
+    use crate::path::{PathHandle, PathInterner};
+
+    fn path(global: bool, segments: &[&'static str]) -> PathId<'static> {
+        let identifiers = segments
+            .iter()
+            .map(|segment| Located::dummy(Id::new(segment).unwrap()))
+            .collect();
+
+        if global {
+            PathId::global(identifiers)
+        } else {
+            PathId::relative(identifiers)
+        }
+    }
+
+    #[test]
+    fn equal_paths_share_handles() {
+        let mut interner = PathInterner::default();
+        let first = interner.intern(&path(true, &["collections", "list"]));
+        let second = interner.intern(&path(true, &["collections", "list"]));
+        let relative = interner.intern(&path(false, &["collections", "list"]));
+
+        assert_eq!(first, second);
+        assert_ne!(first, relative);
+        assert_eq!(
+            Some(interner.intern(&path(true, &["collections"]))),
+            interner.parent(first)
+        );
+        assert_eq!(PathHandle::RELATIVE, interner.intern(&path(false, &[])));
+        assert_eq!(2, interner.len(first));
+        assert_eq!(2, interner.names.len());
+    }
+
+    #[test]
+    fn segments_are_reconstructed() {
+        let mut interner = PathInterner::default();
+        let original = path(true, &["a", "b", "c"]);
+        let handle = interner.intern(&original);
+
+        assert_eq!(
+            vec!["a", "b", "c"],
+            interner
+                .segments(handle)
+                .into_iter()
+                .map(|segment| segment.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(interner.is_global(handle));
+        assert_eq!(original, interner.to_path_id(handle));
+    }
+}
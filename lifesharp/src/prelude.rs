@@ -0,0 +1,9 @@
+//! Items from `alloc` that the standard library prelude would otherwise provide, so that modules compile with or
+//! without the `std` feature.
+
+pub(crate) use alloc::borrow::ToOwned;
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::format;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec;
+pub(crate) use alloc::vec::Vec;
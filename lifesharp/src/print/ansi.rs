@@ -0,0 +1,52 @@
+//! Syntax-highlighted printing of LifeSharp source code for terminals, using ANSI escape sequences.
+
+use crate::lexer::{TokenClass, TokenKind};
+use crate::location::Span;
+use core::fmt::Write;
+
+/// Escape sequence that resets the color of any following text.
+pub const RESET: &str = "\x1b[0m";
+
+/// Gets the escape sequence used to set the color of tokens of the specified kind, or `None` if the tokens are not
+/// colored.
+pub fn color(class: TokenClass) -> Option<&'static str> {
+    match class {
+        TokenClass::Layout | TokenClass::Punctuation | TokenClass::Identifier => None,
+        TokenClass::Operator => Some("\x1b[33m"),
+        TokenClass::Keyword => Some("\x1b[1;35m"),
+        TokenClass::Literal => Some("\x1b[32m"),
+    }
+}
+
+/// Writes the source code with each token colored according to its kind.
+///
+/// The text between tokens, such as whitespace, is written as is.
+pub fn render<I>(output: &mut dyn Write, source: &str, tokens: I) -> super::Result
+where
+    I: IntoIterator<Item = (TokenKind, Span)>,
+{
+    super::highlight(
+        output,
+        source,
+        tokens,
+        |output, trivia| output.write_str(trivia),
+        |output, class, text| match color(class) {
+            Some(color) => write!(output, "{}{}{}", color, text, RESET),
+            None => output.write_str(text),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::TokenKind;
+    use crate::location::{FileId, Span};
+
+    #[test]
+    fn operator_is_colored() {
+        let mut output = String::new();
+        let tokens = [(TokenKind::PlusSign, Span::new(FileId::default(), 0..1))];
+        super::render(&mut output, "+\n", tokens).unwrap();
+        assert_eq!("\x1b[33m+\x1b[0m\n", output);
+    }
+}
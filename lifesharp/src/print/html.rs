@@ -0,0 +1,77 @@
+//! Syntax-highlighted printing of LifeSharp source code as HTML.
+
+use crate::lexer::{TokenClass, TokenKind};
+use crate::location::Span;
+use core::fmt::Write;
+
+/// Gets the CSS class used for tokens of the specified kind.
+pub fn class_name(class: TokenClass) -> &'static str {
+    match class {
+        TokenClass::Layout => "ls-layout",
+        TokenClass::Punctuation => "ls-punctuation",
+        TokenClass::Operator => "ls-operator",
+        TokenClass::Keyword => "ls-keyword",
+        TokenClass::Literal => "ls-literal",
+        TokenClass::Identifier => "ls-identifier",
+    }
+}
+
+fn write_escaped(output: &mut dyn Write, text: &str) -> super::Result {
+    for c in text.chars() {
+        match c {
+            '&' => output.write_str("&amp;")?,
+            '<' => output.write_str("&lt;")?,
+            '>' => output.write_str("&gt;")?,
+            '"' => output.write_str("&quot;")?,
+            '\'' => output.write_str("&#39;")?,
+            _ => output.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the source code as HTML, with each token enclosed in a `span` element whose CSS class is given by
+/// [`class_name`].
+///
+/// The text between tokens, such as whitespace, is written as is. Callers are expected to enclose the output in a `pre`
+/// element.
+pub fn render<I>(output: &mut dyn Write, source: &str, tokens: I) -> super::Result
+where
+    I: IntoIterator<Item = (TokenKind, Span)>,
+{
+    super::highlight(
+        output,
+        source,
+        tokens,
+        write_escaped,
+        |output, class, text| {
+            write!(output, "<span class=\"{}\">", class_name(class))?;
+            write_escaped(output, text)?;
+            output.write_str("</span>")
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::{self, TokenKind};
+    use crate::location::{FileId, Span};
+
+    #[test]
+    fn token_is_escaped_and_enclosed_in_span() {
+        let mut output = String::new();
+        let tokens = [(TokenKind::LessThan, Span::new(FileId::default(), 1..2))];
+        super::render(&mut output, " < ", tokens).unwrap();
+        assert_eq!(" <span class=\"ls-operator\">&lt;</span> ", output);
+    }
+
+    #[test]
+    fn tokenized_input_is_rendered() {
+        let mut output = String::new();
+        let tokens = lexer::tokenize("(", FileId::default(), None).unwrap();
+        let kinds = tokens.iter().map(|token| (token.kind(), token.span()));
+        super::render(&mut output, "(", kinds).unwrap();
+        assert_eq!("<span class=\"ls-punctuation\">(</span>", output);
+    }
+}
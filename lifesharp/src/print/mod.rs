@@ -0,0 +1,975 @@
+//! Printing LifeSharp source code.
+
+#![deny(missing_docs)]
+
+use crate::lexer::{self, TokenClass, TokenKind};
+use crate::location::Span;
+use crate::prelude::*;
+use crate::text;
+use core::fmt::Write;
+use core::ops::ControlFlow;
+#[cfg(feature = "std")]
+use std::io;
+
+pub mod ansi;
+pub mod html;
+
+/// Type returned by functions that print source code.
+pub use core::fmt::Result;
+
+/// Specifies the characters used to indent lines of source code.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum IndentStyle {
+    /// Each indentation level is written as a number of spaces equal to the indent width.
+    Spaces,
+    /// Each indentation level is written as a single tab character, which is assumed to be as wide as the indent width.
+    Tabs,
+}
+
+/// Specifies where the opening curly brace (`{`) of a block is placed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BraceStyle {
+    /// The opening brace is placed at the end of the line that precedes the block.
+    SameLine,
+    /// The opening brace is placed on its own line before the block.
+    NextLine,
+}
+
+/// Specifies how the blocks of function bodies and conditional expressions are delimited.
+///
+/// Any block can be written either way. Indentation is the usual form, while braces allow a block to be written on a
+/// single line, or in generated code that does not track indentation. Indentation is not significant inside braces.
+/// Block expressions are always enclosed in braces, as they have no keyword to mark where an indented block starts.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BlockStyle {
+    /// The expressions of a block are indented on the lines after the line that starts it.
+    #[default]
+    Indentation,
+    /// The expressions of a block are enclosed in curly braces.
+    Braces,
+}
+
+/// Specifies the line breaks written before each element of a list printed with [`Printer::write_list`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LineBreak {
+    /// Elements are printed on the same line.
+    None,
+    /// Each element is printed on its own line if the enclosing group does not fit on one line, and is otherwise
+    /// preceded by a space.
+    Soft,
+    /// Each element is always printed on its own line.
+    Hard,
+}
+
+/// Specifies when a separator is written after the last element of a list printed with [`Printer::write_list`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TrailingSeparator {
+    /// The last element is never followed by a separator.
+    Never,
+    /// The last element is always followed by a separator.
+    Always,
+    /// The last element is followed by a separator only if the enclosing group is broken across lines.
+    IfBroken,
+}
+
+/// Controls how [`Printer::write_list`] prints the elements of a list.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ListStyle<'s> {
+    separator: &'s str,
+    trailing_separator: TrailingSeparator,
+    line_break: LineBreak,
+    break_after_last: bool,
+    indented: bool,
+    empty: &'s str,
+}
+
+impl<'s> ListStyle<'s> {
+    /// Creates a style for a list whose elements are separated by the specified text and printed on the same line.
+    pub const fn new(separator: &'s str) -> Self {
+        Self {
+            separator,
+            trailing_separator: TrailingSeparator::Never,
+            line_break: LineBreak::None,
+            break_after_last: true,
+            indented: false,
+            empty: "",
+        }
+    }
+
+    /// Specifies when the last element is followed by a separator.
+    pub const fn with_trailing_separator(self, trailing_separator: TrailingSeparator) -> Self {
+        Self {
+            trailing_separator,
+            ..self
+        }
+    }
+
+    /// Specifies the line breaks written before each element, which are also written after the last element so that
+    /// any closing delimiter is placed on its own line.
+    pub const fn with_line_break(self, line_break: LineBreak) -> Self {
+        Self { line_break, ..self }
+    }
+
+    /// Specifies that no line break is written after the last element, for lists that are not followed by a closing
+    /// delimiter.
+    pub const fn without_break_after_last(self) -> Self {
+        Self {
+            break_after_last: false,
+            ..self
+        }
+    }
+
+    /// Specifies that the elements are indented by one level.
+    pub const fn indented(self) -> Self {
+        Self {
+            indented: true,
+            ..self
+        }
+    }
+
+    /// Specifies the text that is written instead when the list is empty.
+    pub const fn with_empty(self, empty: &'s str) -> Self {
+        Self { empty, ..self }
+    }
+}
+
+/// Controls how source code is printed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Options {
+    /// The number of columns of each indentation level.
+    pub indent_width: usize,
+    /// Specifies whether spaces or tabs are used for indentation.
+    pub indent_style: IndentStyle,
+    /// The maximum display width of a line, groups that would exceed this width are broken across lines.
+    ///
+    /// Widths are measured with [`text::display_width`], so wide characters count as two columns.
+    pub max_width: usize,
+    /// If `true`, source files end with a newline.
+    pub trailing_newline: bool,
+    /// Specifies where opening curly braces are placed.
+    pub brace_style: BraceStyle,
+    /// Specifies how blocks are delimited, allowing source code written with either style to be normalized to one.
+    pub block_style: BlockStyle,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            indent_style: IndentStyle::Spaces,
+            max_width: 100,
+            trailing_newline: true,
+            brace_style: BraceStyle::SameLine,
+            block_style: BlockStyle::Indentation,
+        }
+    }
+}
+
+/// Content buffered while a group is open, so that the group can be printed on one line or broken across lines once its
+/// full contents are known.
+#[derive(Debug)]
+enum Element {
+    Text(String),
+    /// A line break that is replaced by the specified text when the enclosing group fits on one line.
+    SoftLine(&'static str),
+    /// Text that is only printed when the enclosing group is broken across lines.
+    IfBroken(String),
+    Newline,
+    Indent,
+    Dedent,
+    Group(Vec<Element>),
+}
+
+impl Element {
+    /// Gets the display width of the element when printed on one line, or `None` if the element contains a newline.
+    fn flat_width(&self, tab_width: usize) -> Option<usize> {
+        match self {
+            Self::Text(text) => Some(text::display_width(text, tab_width)),
+            Self::SoftLine(flat) => Some(text::display_width(flat, tab_width)),
+            Self::IfBroken(_) => Some(0),
+            Self::Newline => None,
+            Self::Indent | Self::Dedent => Some(0),
+            Self::Group(elements) => flat_width(elements, tab_width),
+        }
+    }
+}
+
+fn flat_width(elements: &[Element], tab_width: usize) -> Option<usize> {
+    elements.iter().try_fold(0, |width, element| {
+        Some(width + element.flat_width(tab_width)?)
+    })
+}
+
+/// Gets the display width of the text printed before the first line break in the elements, assuming that any groups in
+/// them are broken across lines.
+///
+/// Returns [`ControlFlow::Continue`] with the width of all of the elements if they contain no line break, in which case
+/// the line continues with whatever follows the elements.
+fn width_until_break(elements: &[Element], tab_width: usize) -> ControlFlow<usize, usize> {
+    elements.iter().try_fold(0, |width, element| match element {
+        Element::Text(text) | Element::IfBroken(text) => {
+            ControlFlow::Continue(width + text::display_width(text, tab_width))
+        }
+        Element::SoftLine(_) | Element::Newline => ControlFlow::Break(width),
+        Element::Indent | Element::Dedent => ControlFlow::Continue(width),
+        Element::Group(elements) => match width_until_break(elements, tab_width) {
+            ControlFlow::Break(inner) => ControlFlow::Break(width + inner),
+            ControlFlow::Continue(inner) => ControlFlow::Continue(width + inner),
+        },
+    })
+}
+
+/// Used for printing source code.
+///
+/// Text following a group on the same line is kept until the line ends, so [`Printer::finish`] must be called once
+/// printing is done.
+pub struct Printer<'o> {
+    output: &'o mut dyn Write,
+    indent_level: usize,
+    /// If `true`, indicates that indentation has not yet been written for the current line of source code.
+    write_indent: bool,
+    options: Options,
+    /// The display width of the text written on the current line, including indentation, where tabs are as wide as the
+    /// indent width.
+    column: usize,
+    /// The contents of the groups that have not yet been closed, with the innermost group last.
+    groups: Vec<Vec<Element>>,
+    /// The contents of the current line, starting with a group, that have not yet been written. A group is only printed
+    /// once the rest of its line is known, so that the text after it is included when checking if it fits.
+    line: Vec<Element>,
+}
+
+impl<'o> Printer<'o> {
+    /// Creates a printer that writes source code to the specified output, such as a `Formatter` or a `String`.
+    pub fn new(output: &'o mut dyn Write) -> Self {
+        Self::new_with_options(output, Options::default())
+    }
+
+    /// Creates a printer that writes source code to the specified output with the specified options.
+    pub fn new_with_options(output: &'o mut dyn Write, options: Options) -> Self {
+        Self {
+            output,
+            indent_level: 0,
+            write_indent: true,
+            options,
+            column: 0,
+            groups: Vec::new(),
+            line: Vec::new(),
+        }
+    }
+
+    /// Gets the options used to print source code.
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Buffers an element in the innermost open group, or in the current line if a group was printed on it, returning
+    /// `false` if the element should be written immediately instead.
+    fn push_element(&mut self, element: Element) -> bool {
+        if let Some(group) = self.groups.last_mut() {
+            group.push(element);
+            true
+        } else if !self.line.is_empty() {
+            self.line.push(element);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Increases the indentation level of any following indentation that is written.
+    pub fn indent(&mut self) {
+        if !self.push_element(Element::Indent) {
+            self.indent_level += 1;
+        }
+    }
+
+    /// Decreases the indentation level of any following indentation that is written.
+    ///
+    /// Decreasing the indentation level below zero has no effect. Prefer [`Printer::indented`], which ensures that every
+    /// increase in the indentation level has a matching decrease.
+    pub fn dedent(&mut self) {
+        if !self.push_element(Element::Dedent) {
+            self.indent_level = self.indent_level.saturating_sub(1);
+        }
+    }
+
+    /// Increases the indentation level, returning a guard that decreases the indentation level when it is dropped.
+    pub fn indented(&mut self) -> IndentGuard<'_, 'o> {
+        self.indent();
+        IndentGuard { printer: self }
+    }
+
+    fn write_indentation(&mut self) -> Result {
+        if self.write_indent {
+            for _ in 0..self.indent_level {
+                match self.options.indent_style {
+                    IndentStyle::Spaces => {
+                        for _ in 0..self.options.indent_width {
+                            self.output.write_char(' ')?;
+                        }
+                    }
+                    IndentStyle::Tabs => self.output.write_char('\t')?,
+                }
+            }
+
+            self.column = self.indent_level * self.options.indent_width;
+            self.write_indent = false;
+        }
+
+        Ok(())
+    }
+
+    fn current_column(&self) -> usize {
+        if self.write_indent {
+            self.indent_level * self.options.indent_width
+        } else {
+            self.column
+        }
+    }
+
+    fn emit_newline(&mut self) -> Result {
+        self.write_indent = true;
+        self.column = 0;
+        self.output.write_char('\n')
+    }
+
+    fn emit_str(&mut self, s: &str) -> Result {
+        self.write_indentation()?;
+        self.column += text::display_width(s, self.options.indent_width);
+        self.output.write_str(s)
+    }
+
+    /// Writes buffered elements, where `trailing` is the display width of the text that follows them on the same line.
+    fn emit_elements(&mut self, elements: Vec<Element>, flat: bool, trailing: usize) -> Result {
+        let mut elements = elements.into_iter();
+        while let Some(element) = elements.next() {
+            match element {
+                Element::Text(text) => self.emit_str(&text)?,
+                Element::SoftLine(text) if flat => self.emit_str(text)?,
+                Element::SoftLine(_) | Element::Newline => self.emit_newline()?,
+                Element::IfBroken(_) if flat => (),
+                Element::IfBroken(text) => self.emit_str(&text)?,
+                Element::Indent => self.indent_level += 1,
+                Element::Dedent => self.indent_level = self.indent_level.saturating_sub(1),
+                Element::Group(group) => {
+                    let trailing =
+                        match width_until_break(elements.as_slice(), self.options.indent_width) {
+                            ControlFlow::Break(width) => width,
+                            ControlFlow::Continue(width) => width + trailing,
+                        };
+
+                    self.emit_group(group, flat, trailing)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a group, which is printed on one line if it and the `trailing` text after it fit within the maximum width.
+    fn emit_group(&mut self, elements: Vec<Element>, flat: bool, trailing: usize) -> Result {
+        let fits = flat
+            || flat_width(&elements, self.options.indent_width).is_some_and(|width| {
+                self.current_column() + width + trailing <= self.options.max_width
+            });
+
+        self.emit_elements(elements, fits, trailing)
+    }
+
+    fn emit_line(&mut self) -> Result {
+        let line = core::mem::take(&mut self.line);
+        self.emit_elements(line, false, 0)
+    }
+
+    /// Writes any text that is kept until the end of the current line, which must be done once printing is done.
+    pub fn finish(mut self) -> Result {
+        self.emit_line()
+    }
+
+    /// Prints content as a group, which is printed on a single line if it fits within the maximum width, with any soft
+    /// lines in the group replaced by spaces or omitted.
+    ///
+    /// If the group contains a newline or does not fit, then each of its soft lines are printed as newlines. Groups can be
+    /// nested, and an inner group is only broken if it does not fit on its own.
+    pub fn group<F: FnOnce(&mut Self) -> Result>(&mut self, content: F) -> Result {
+        self.groups.push(Vec::new());
+        let result = content(self);
+        let elements = self.groups.pop().expect("group should have been open");
+        result?;
+
+        match self.groups.last_mut() {
+            Some(parent) => parent.push(Element::Group(elements)),
+            None => self.line.push(Element::Group(elements)),
+        }
+
+        Ok(())
+    }
+
+    /// Writes a line break that is printed as a space if the enclosing group fits on one line.
+    ///
+    /// Outside of a group, this always writes a newline.
+    pub fn soft_line(&mut self) -> Result {
+        self.write_soft_line(" ")
+    }
+
+    /// Writes a line break that is omitted if the enclosing group fits on one line.
+    ///
+    /// Outside of a group, this always writes a newline.
+    pub fn soft_break(&mut self) -> Result {
+        self.write_soft_line("")
+    }
+
+    fn write_soft_line(&mut self, flat: &'static str) -> Result {
+        if self.groups.is_empty() {
+            self.newline()
+        } else {
+            self.push_element(Element::SoftLine(flat));
+            Ok(())
+        }
+    }
+
+    /// Writes text that is only printed if the enclosing group is broken across lines, such as a trailing comma.
+    ///
+    /// Outside of a group, the text is always written.
+    pub fn write_if_broken(&mut self, s: &str) -> Result {
+        if self.groups.is_empty() {
+            self.write_str(s)
+        } else {
+            self.push_element(Element::IfBroken(s.to_owned()));
+            Ok(())
+        }
+    }
+
+    /// Writes a newline into the source code, indicating that indentation must be written in the new line.
+    ///
+    /// Use this as the primary means to emit newlines into the output, as other methods will not indicate that a indentation
+    /// must be written. A newline forces any enclosing groups to be broken across lines.
+    pub fn newline(&mut self) -> Result {
+        if let Some(group) = self.groups.last_mut() {
+            group.push(Element::Newline);
+            Ok(())
+        } else {
+            self.emit_line()?;
+            self.emit_newline()
+        }
+    }
+
+    /// Writes a character to the output.
+    pub fn write_char(&mut self, c: char) -> Result {
+        self.write_str(c.encode_utf8(&mut [0u8; 4]))
+    }
+
+    /// Writes a string into the output.
+    pub fn write_str(&mut self, s: &str) -> Result {
+        if self.push_element(Element::Text(s.to_owned())) {
+            Ok(())
+        } else {
+            self.emit_str(s)
+        }
+    }
+
+    /// Writes the formatted arguments into the output.
+    pub fn write_fmt(&mut self, f: core::fmt::Arguments<'_>) -> Result {
+        match f.as_str() {
+            Some(s) => self.write_str(s),
+            None => self.write_str(&f.to_string()),
+        }
+    }
+
+    /// Prints the elements returned by an iterator, separated by the specified separator.
+    pub fn write_iter<T: Print, S: Print, I: IntoIterator<Item = T>>(
+        &mut self,
+        content: I,
+        separator: S,
+    ) -> Result {
+        for (index, item) in content.into_iter().enumerate() {
+            if index > 0 {
+                separator.print(self)?;
+            }
+
+            item.print(self)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_line_break(&mut self, line_break: LineBreak) -> Result {
+        match line_break {
+            LineBreak::None => Ok(()),
+            LineBreak::Soft => self.soft_line(),
+            LineBreak::Hard => self.newline(),
+        }
+    }
+
+    /// Prints the elements returned by an iterator as a list, using the specified style to control separators, line
+    /// breaks, and indentation.
+    ///
+    /// The list is not enclosed in a group, so soft line breaks depend on the group that the caller opens around the list
+    /// and its delimiters.
+    pub fn write_list<T: Print, I: IntoIterator<Item = T>>(
+        &mut self,
+        content: I,
+        style: ListStyle<'_>,
+    ) -> Result {
+        let mut content = content.into_iter().peekable();
+        if content.peek().is_none() {
+            return self.write_str(style.empty);
+        }
+
+        if style.indented {
+            self.indent();
+        }
+
+        while let Some(item) = content.next() {
+            self.write_line_break(style.line_break)?;
+            item.print(self)?;
+
+            if content.peek().is_some() {
+                self.write_str(style.separator)?;
+            } else {
+                match style.trailing_separator {
+                    TrailingSeparator::Never => (),
+                    TrailingSeparator::Always => self.write_str(style.separator)?,
+                    TrailingSeparator::IfBroken => self.write_if_broken(style.separator)?,
+                }
+            }
+        }
+
+        if style.indented {
+            self.dedent();
+        }
+
+        if style.break_after_last {
+            self.write_line_break(style.line_break)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Increases the indentation level of a [`Printer`] for as long as it is in scope.
+///
+/// Returned by [`Printer::indented`], and dereferences to the printer.
+pub struct IndentGuard<'p, 'o> {
+    printer: &'p mut Printer<'o>,
+}
+
+impl<'o> core::ops::Deref for IndentGuard<'_, 'o> {
+    type Target = Printer<'o>;
+
+    fn deref(&self) -> &Printer<'o> {
+        self.printer
+    }
+}
+
+impl<'o> core::ops::DerefMut for IndentGuard<'_, 'o> {
+    fn deref_mut(&mut self) -> &mut Printer<'o> {
+        self.printer
+    }
+}
+
+impl Drop for IndentGuard<'_, '_> {
+    fn drop(&mut self) {
+        self.printer.dedent();
+    }
+}
+
+/// Trait implemented by types that represent AST nodes to print source code.
+pub trait Print {
+    /// Prints source code.
+    fn print(&self, printer: &mut Printer) -> Result;
+}
+
+impl<T: Print + ?Sized> Print for &T {
+    fn print(&self, printer: &mut Printer) -> Result {
+        <T as Print>::print(self, printer)
+    }
+}
+
+impl Print for str {
+    fn print(&self, printer: &mut Printer) -> Result {
+        printer.write_str(self)
+    }
+}
+
+impl Print for () {
+    fn print(&self, _: &mut Printer) -> Result {
+        Ok(())
+    }
+}
+
+/// Prints source code into a `String` with the specified options.
+pub fn to_string_with_options<P: Print + ?Sized>(content: &P, options: Options) -> String {
+    let mut output = String::new();
+    let mut printer = Printer::new_with_options(&mut output, options);
+    content
+        .print(&mut printer)
+        .and_then(|()| printer.finish())
+        .expect("printing into a String should not fail");
+    output
+}
+
+/// Prints source code into a `String` with the default options.
+pub fn to_string<P: Print + ?Sized>(content: &P) -> String {
+    to_string_with_options(content, Options::default())
+}
+
+/// Adapts an [`io::Write`] to allow printing source code into it, keeping any I/O error that occurs.
+#[cfg(feature = "std")]
+struct IoOutput<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Write for IoOutput<W> {
+    fn write_str(&mut self, s: &str) -> Result {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            core::fmt::Error
+        })
+    }
+}
+
+/// Prints source code into the specified writer with the specified options.
+#[cfg(feature = "std")]
+pub fn to_writer_with_options<W: io::Write, P: Print + ?Sized>(
+    writer: W,
+    content: &P,
+    options: Options,
+) -> io::Result<()> {
+    let mut output = IoOutput {
+        writer,
+        error: None,
+    };
+
+    let mut printer = Printer::new_with_options(&mut output, options);
+    match content.print(&mut printer).and_then(|()| printer.finish()) {
+        Ok(()) => Ok(()),
+        Err(core::fmt::Error) => Err(output
+            .error
+            .unwrap_or_else(|| io::Error::other("error printing source code"))),
+    }
+}
+
+/// Prints source code into the specified writer with the default options.
+#[cfg(feature = "std")]
+pub fn to_writer<W: io::Write, P: Print + ?Sized>(writer: W, content: &P) -> io::Result<()> {
+    to_writer_with_options(writer, content, Options::default())
+}
+
+/// Writes the text of each token in the source code, along with the text between tokens such as whitespace and comments.
+fn highlight<I, T, C>(
+    output: &mut dyn Write,
+    source: &str,
+    tokens: I,
+    write_trivia: T,
+    write_token: C,
+) -> Result
+where
+    I: IntoIterator<Item = (TokenKind, Span)>,
+    T: Fn(&mut dyn Write, &str) -> Result,
+    C: Fn(&mut dyn Write, TokenClass, &str) -> Result,
+{
+    let mut previous_end = 0;
+
+    for (kind, location) in tokens {
+        let text = match source.get(location.range()) {
+            Some(text) if !text.is_empty() && location.start() >= previous_end => text,
+            _ => continue,
+        };
+
+        write_trivia(output, &source[previous_end..location.start()])?;
+        write_token(output, kind.class(), text)?;
+        previous_end = location.end();
+    }
+
+    write_trivia(output, &source[previous_end..])
+}
+
+/// Writes a listing of the tokens from a source file, with each token on its own line along with its line and column
+/// number.
+///
+/// Useful when debugging the lexer or the parser.
+pub fn write_token_listing(output: &mut dyn Write, tokens: &lexer::Output<'_>) -> Result {
+    for (token, offsets) in tokens.tokens() {
+        match tokens.locations().get(offsets.start()) {
+            Some(location) => write!(
+                output,
+                "{}:{}",
+                location.line_number(),
+                location.column_number()
+            )?,
+            None => output.write_char('?')?,
+        }
+
+        writeln!(output, "\t{:?}\t{}", token, token)?;
+    }
+
+    Ok(())
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! print_display_impl {
+    ($implementor: ty) => {
+        impl core::fmt::Display for $implementor {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> $crate::print::Result {
+                let mut printer = $crate::print::Printer::new(f);
+                $crate::print::Print::print(&self, &mut printer)?;
+                printer.finish()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::print::{
+        self, IndentStyle, LineBreak, ListStyle, Options, Printer, TrailingSeparator,
+    };
+    use core::fmt::{Display, Formatter};
+
+    struct Printed<F>(Options, F);
+
+    impl<F: Fn(&mut Printer) -> print::Result> Display for Printed<F> {
+        fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+            let mut printer = Printer::new_with_options(f, self.0.clone());
+            (self.1)(&mut printer)?;
+            printer.finish()
+        }
+    }
+
+    fn max_width(max_width: usize) -> Options {
+        Options {
+            max_width,
+            ..Options::default()
+        }
+    }
+
+    fn print_list(printer: &mut Printer) -> print::Result {
+        printer.group(|printer| {
+            printer.write_char('[')?;
+
+            {
+                let mut printer = printer.indented();
+                printer.soft_break()?;
+                printer.write_str("first,")?;
+                printer.soft_line()?;
+                printer.write_str("second")?;
+            }
+
+            printer.soft_break()?;
+            printer.write_char(']')
+        })
+    }
+
+    #[test]
+    fn group_that_fits_is_flat() {
+        assert_eq!(
+            "[first, second]",
+            Printed(max_width(15), print_list).to_string()
+        );
+    }
+
+    #[test]
+    fn group_that_does_not_fit_is_broken() {
+        assert_eq!(
+            "[\n    first,\n    second\n]",
+            Printed(max_width(14), print_list).to_string()
+        );
+    }
+
+    #[test]
+    fn group_is_broken_by_text_after_it_on_the_same_line() {
+        let print_statement = |printer: &mut Printer| {
+            print_list(printer)?;
+            printer.write_char(';')?;
+            printer.newline()?;
+            printer.write_str("next line")
+        };
+
+        assert_eq!(
+            "[first, second];\nnext line",
+            Printed(max_width(16), print_statement).to_string()
+        );
+        assert_eq!(
+            "[\n    first,\n    second\n];\nnext line",
+            Printed(max_width(15), print_statement).to_string()
+        );
+    }
+
+    #[test]
+    fn wide_characters_count_as_two_columns() {
+        let print_pair = |printer: &mut Printer| {
+            printer.group(|printer| {
+                printer.write_str("名前")?;
+                printer.soft_line()?;
+                printer.write_str("値")
+            })
+        };
+
+        assert_eq!("名前 値", Printed(max_width(7), print_pair).to_string());
+        assert_eq!("名前\n値", Printed(max_width(6), print_pair).to_string());
+    }
+
+    #[test]
+    fn group_containing_newline_is_broken() {
+        let printed = Printed(max_width(100), |printer: &mut Printer| {
+            printer.group(|printer| {
+                printer.write_str("a")?;
+                printer.soft_line()?;
+                printer.write_str("b")?;
+                printer.newline()?;
+                printer.write_str("c")
+            })
+        });
+
+        assert_eq!("a\nb\nc", printed.to_string());
+    }
+
+    #[test]
+    fn inner_group_is_flat_when_outer_group_is_broken() {
+        let printed = Printed(max_width(20), |printer: &mut Printer| {
+            printer.group(|printer| {
+                printer.write_str("outer")?;
+                printer.soft_line()?;
+                print_list(printer)?;
+                printer.soft_line()?;
+                printer.write_str("end")
+            })
+        });
+
+        assert_eq!("outer\n[first, second]\nend", printed.to_string());
+    }
+
+    #[test]
+    fn indentation_uses_options() {
+        let options = Options {
+            indent_style: IndentStyle::Tabs,
+            ..max_width(0)
+        };
+        assert_eq!(
+            "[\n\tfirst,\n\tsecond\n]",
+            Printed(options, print_list).to_string()
+        );
+
+        let options = Options {
+            indent_width: 2,
+            ..max_width(0)
+        };
+        assert_eq!(
+            "[\n  first,\n  second\n]",
+            Printed(options, print_list).to_string()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn printing_to_string_and_writer_produce_same_output() {
+        struct List;
+
+        impl print::Print for List {
+            fn print(&self, printer: &mut Printer) -> print::Result {
+                print_list(printer)
+            }
+        }
+
+        let mut written = Vec::new();
+        print::to_writer(&mut written, &List).unwrap();
+        assert_eq!(print::to_string(&List).as_bytes(), written.as_slice());
+    }
+
+    #[test]
+    fn unbalanced_dedent_is_ignored() {
+        let printed = Printed(Options::default(), |printer: &mut Printer| {
+            printer.dedent();
+            printer.write_str("a")?;
+            printer.group(|printer| {
+                printer.dedent();
+                printer.write_str("b")
+            })?;
+            printer.newline()?;
+            printer.write_str("c")
+        });
+
+        assert_eq!("ab\nc", printed.to_string());
+    }
+
+    #[test]
+    fn token_listing_includes_locations() {
+        let tokens =
+            crate::lexer::tokenize("()\n\n[+]", crate::location::FileId::default(), None).unwrap();
+        let mut output = String::new();
+        print::write_token_listing(&mut output, &tokens).unwrap();
+        assert_eq!(
+            "1:1\tOpenParenthesis\t(\n\
+             1:2\tCloseParenthesis\t)\n\
+             3:1\tOpenSquareBracket\t[\n\
+             3:2\tPlusSign\t+\n\
+             3:3\tCloseSquareBracket\t]\n",
+            output
+        );
+    }
+
+    fn print_record(printer: &mut Printer) -> print::Result {
+        let style = ListStyle::new(",")
+            .with_line_break(LineBreak::Soft)
+            .with_trailing_separator(TrailingSeparator::IfBroken)
+            .indented()
+            .with_empty("{}");
+
+        printer.group(|printer| {
+            printer.write_char('{')?;
+            printer.write_list(["first", "second"], style)?;
+            printer.write_char('}')
+        })?;
+
+        printer.write_char(' ')?;
+        printer.group(|printer| printer.write_list(Vec::<&str>::new(), style))
+    }
+
+    #[test]
+    fn list_trailing_separator_is_only_written_when_broken() {
+        assert_eq!(
+            "{ first, second } {}",
+            Printed(max_width(20), print_record).to_string()
+        );
+        assert_eq!(
+            "{\n    first,\n    second,\n} {}",
+            Printed(max_width(10), print_record).to_string()
+        );
+    }
+
+    #[test]
+    fn list_with_hard_line_breaks() {
+        let printed = Printed(Options::default(), |printer: &mut Printer| {
+            printer.write_list(
+                ["a", "b"],
+                ListStyle::new(";")
+                    .with_line_break(LineBreak::Hard)
+                    .with_trailing_separator(TrailingSeparator::Always),
+            )
+        });
+
+        assert_eq!("\na;\nb;\n", printed.to_string());
+    }
+
+    #[test]
+    fn list_without_break_after_last() {
+        let printed = Printed(Options::default(), |printer: &mut Printer| {
+            printer.write_str("f")?;
+            printer.write_list(
+                ["a", "b"],
+                ListStyle::new(";")
+                    .with_line_break(LineBreak::Hard)
+                    .without_break_after_last(),
+            )?;
+            printer.write_str(" =")
+        });
+
+        assert_eq!("f\na;\nb =", printed.to_string());
+    }
+}
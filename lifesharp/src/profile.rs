@@ -0,0 +1,130 @@
+//! Measures how long each pass of the compiler takes, writing the measurements in the
+//! [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU) so
+//! that they can be viewed as a flame graph in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use std::fmt::Write as _;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Describes a single run of a pass.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Event {
+    /// The name of the pass, such as `lexing`.
+    pub name: &'static str,
+    /// The time at which the pass started, relative to when the profiler was created.
+    pub start: Duration,
+    /// How long the pass took, including any passes that it ran.
+    pub duration: Duration,
+}
+
+/// Records the start and end times of passes.
+#[derive(Debug)]
+pub struct Profiler {
+    origin: Instant,
+    events: Vec<Event>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            events: Vec::new(),
+        }
+    }
+}
+
+fn escape_json(s: &str, output: &mut String) {
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(output, "\\u{:04x}", u32::from(c));
+            }
+            c => output.push(c),
+        }
+    }
+}
+
+impl Profiler {
+    /// Runs a pass, recording how long it takes.
+    ///
+    /// The pass is given the profiler, so that it can measure the passes that it runs.
+    pub fn time<T, F: FnOnce(&mut Self) -> T>(&mut self, name: &'static str, pass: F) -> T {
+        let index = self.events.len();
+        let start = self.origin.elapsed();
+        self.events.push(Event {
+            name,
+            start,
+            duration: Duration::ZERO,
+        });
+
+        let result = pass(self);
+        self.events[index].duration = self.origin.elapsed() - start;
+        result
+    }
+
+    /// Gets the recorded events, in the order that the passes started in.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Converts the recorded events into a Chrome trace, in which every event is a complete (`X`) event on the same
+    /// thread.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace = String::from("{\"traceEvents\":[");
+        for (index, event) in self.events.iter().enumerate() {
+            if index > 0 {
+                trace.push(',');
+            }
+
+            trace.push_str("{\"name\":\"");
+            escape_json(event.name, &mut trace);
+            let _ = write!(
+                trace,
+                "\",\"cat\":\"pass\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":0}}",
+                event.start.as_micros(),
+                event.duration.as_micros()
+            );
+        }
+
+        trace.push_str("]}");
+        trace
+    }
+
+    /// Writes the recorded events as a Chrome trace.
+    pub fn write_chrome_trace<W: io::Write + ?Sized>(&self, output: &mut W) -> io::Result<()> {
+        output.write_all(self.to_chrome_trace().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::profile::Profiler;
+
+    #[test]
+    fn nested_passes_are_recorded() {
+        let mut profiler = Profiler::default();
+        let tokens = profiler.time("parsing", |profiler| {
+            profiler.time("lexing", |_| 3) + profiler.time("lexing", |_| 4)
+        });
+
+        let events = profiler.events();
+        assert_eq!(7, tokens);
+        assert_eq!(
+            vec!["parsing", "lexing", "lexing"],
+            events.iter().map(|event| event.name).collect::<Vec<_>>()
+        );
+        assert!(events[1].start >= events[0].start);
+        assert!(events[2].start + events[2].duration <= events[0].start + events[0].duration);
+
+        let trace = profiler.to_chrome_trace();
+        assert!(trace
+            .starts_with("{\"traceEvents\":[{\"name\":\"parsing\",\"cat\":\"pass\",\"ph\":\"X\","));
+        assert_eq!(3, trace.matches("\"ph\":\"X\"").count());
+        assert!(trace.ends_with("\"pid\":0,\"tid\":0}]}"));
+    }
+}
@@ -0,0 +1,493 @@
+//! Reading of LifeSharp projects, which are described by a `life.toml` manifest file in the project's root directory.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::identifier::{self, Id, Identifier};
+use crate::limits::Limits;
+use crate::target::{Target, UnknownTargetError};
+use crate::vfs::{DiskFileSystem, FileSystem};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The name of the manifest file in the root directory of a project.
+pub const MANIFEST_FILE_NAME: &str = "life.toml";
+
+/// The file extension of LifeSharp source files.
+pub const SOURCE_FILE_EXTENSION: &str = "lfs";
+
+/// Indicates what is produced when a package is compiled.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    /// A library that can be used by other packages.
+    Lib,
+    /// An executable program.
+    #[default]
+    Exe,
+}
+
+fn default_source_directories() -> Vec<PathBuf> {
+    vec![PathBuf::from("src")]
+}
+
+/// The `[package]` section of a manifest, which describes the package.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct Package {
+    /// The name of the package, which must be a valid identifier.
+    pub name: String,
+    /// Specifies what is produced when the package is compiled.
+    #[serde(default)]
+    pub kind: TargetKind,
+    /// The directories containing the source files of the package, relative to the project root.
+    #[serde(default = "default_source_directories")]
+    pub source_directories: Vec<PathBuf>,
+}
+
+/// An entry in the `[dependencies]` section of a manifest.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[non_exhaustive]
+pub struct Dependency {
+    /// The path to the root directory of the dependency, relative to the project root.
+    pub path: PathBuf,
+}
+
+/// The `[compiler]` section of a manifest, which configures how the package is compiled.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct CompilerOptions {
+    /// The triple of the target that the package is compiled for, or `None` to compile for the host.
+    pub target: Option<String>,
+    /// Limits on the size and complexity of the package's source code.
+    pub limits: Limits,
+}
+
+impl CompilerOptions {
+    /// Gets the description of the target that the package is compiled for, or `None` if no target was specified.
+    pub fn target(&self) -> Result<Option<Target>, UnknownTargetError> {
+        self.target.as_deref().map(Target::from_triple).transpose()
+    }
+}
+
+/// The contents of a `life.toml` manifest file.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[non_exhaustive]
+pub struct Manifest {
+    /// Describes the package.
+    pub package: Package,
+    /// The packages that this package depends on, keyed by name.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, Dependency>,
+    /// Configures how the package is compiled.
+    #[serde(default)]
+    pub compiler: CompilerOptions,
+}
+
+impl Manifest {
+    /// Parses the contents of a manifest file.
+    pub fn from_toml(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+}
+
+/// Error used when a project could not be loaded.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Used when a file or directory could not be read.
+    #[error("could not read {}: {source}", path.display())]
+    Io {
+        /// The path to the file or directory.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+    /// Used when a manifest file is not valid.
+    #[error("invalid manifest {}: {source}", path.display())]
+    InvalidManifest {
+        /// The path to the manifest file.
+        path: PathBuf,
+        /// The underlying error.
+        source: toml::de::Error,
+    },
+    /// Used when the name of a package is not a valid identifier.
+    #[error("invalid package name {name:?}: {source}")]
+    InvalidPackageName {
+        /// The name of the package.
+        name: String,
+        /// Describes why the name is not valid.
+        source: identifier::InvalidError,
+    },
+    /// Used when the path of a source file cannot be used as the name of a module.
+    #[error("{} is not a valid module name: {source}", path.display())]
+    InvalidModuleName {
+        /// The path to the source file.
+        path: PathBuf,
+        /// Describes why the name is not valid.
+        source: identifier::InvalidError,
+    },
+    /// Used when packages depend on each other.
+    #[error("dependency cycle between packages {}", cycle.join(" -> "))]
+    DependencyCycle {
+        /// The names of the packages in the cycle, starting and ending with the same package.
+        cycle: Vec<String>,
+    },
+}
+
+/// A source file in a project.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SourceFile {
+    /// The path to the source file.
+    pub path: PathBuf,
+    /// The name of the module defined by the source file, derived from its path relative to its source directory (e.g.
+    /// `src/collections/list.lfs` defines `collections\list`).
+    pub module: Vec<Identifier>,
+}
+
+/// A package loaded from a project directory.
+#[derive(Clone, Debug)]
+pub struct Project {
+    root: PathBuf,
+    manifest: Manifest,
+    source_files: Vec<SourceFile>,
+}
+
+fn read_directory(file_system: &dyn FileSystem, path: &Path) -> Result<Vec<PathBuf>, Error> {
+    file_system.read_dir(path).map_err(|source| Error::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn module_name(relative_path: &Path) -> Result<Vec<Identifier>, identifier::InvalidError> {
+    relative_path
+        .with_extension("")
+        .components()
+        .map(|component| {
+            let name = component.as_os_str().to_str().unwrap_or_default();
+            Id::new(name).map(Id::to_identifier)
+        })
+        .collect()
+}
+
+fn find_source_files(
+    file_system: &dyn FileSystem,
+    source_directory: &Path,
+    directory: &Path,
+    source_files: &mut Vec<SourceFile>,
+) -> Result<(), Error> {
+    for path in read_directory(file_system, directory)? {
+        if file_system.is_dir(&path) {
+            find_source_files(file_system, source_directory, &path, source_files)?;
+        } else if path.extension() == Some(SOURCE_FILE_EXTENSION.as_ref()) {
+            let relative_path = path.strip_prefix(source_directory).unwrap_or(&path);
+            match module_name(relative_path) {
+                Ok(module) => source_files.push(SourceFile { path, module }),
+                Err(source) => return Err(Error::InvalidModuleName { path, source }),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Project {
+    /// Loads the project in the specified directory, reading its manifest and finding its source files.
+    pub fn load<P: Into<PathBuf>>(root: P) -> Result<Self, Error> {
+        Self::load_from(&DiskFileSystem, root)
+    }
+
+    /// Loads the project in the specified directory of a file system, reading its manifest and finding its source
+    /// files.
+    pub fn load_from<P: Into<PathBuf>>(
+        file_system: &dyn FileSystem,
+        root: P,
+    ) -> Result<Self, Error> {
+        let root = root.into();
+        let manifest_path = root.join(MANIFEST_FILE_NAME);
+        let contents = file_system
+            .read_to_string(&manifest_path)
+            .map_err(|source| Error::Io {
+                path: manifest_path.clone(),
+                source,
+            })?;
+
+        let manifest = Manifest::from_toml(&contents).map_err(|source| Error::InvalidManifest {
+            path: manifest_path,
+            source,
+        })?;
+
+        if let Err(source) = Id::new(&manifest.package.name) {
+            return Err(Error::InvalidPackageName {
+                name: manifest.package.name,
+                source,
+            });
+        }
+
+        let mut source_files = Vec::new();
+        for source_directory in manifest.package.source_directories.iter() {
+            let source_directory = root.join(source_directory);
+            find_source_files(
+                file_system,
+                &source_directory,
+                &source_directory,
+                &mut source_files,
+            )?;
+        }
+
+        Ok(Self {
+            root,
+            manifest,
+            source_files,
+        })
+    }
+
+    /// Loads the project in the specified directory along with all of its dependencies, returning the projects in the
+    /// order that they should be compiled, with dependencies before the packages that use them.
+    pub fn load_with_dependencies<P: Into<PathBuf>>(root: P) -> Result<Vec<Self>, Error> {
+        Self::load_with_dependencies_from(&DiskFileSystem, root)
+    }
+
+    /// Loads the project in the specified directory of a file system along with all of its dependencies, see
+    /// [`Project::load_with_dependencies`].
+    pub fn load_with_dependencies_from<P: Into<PathBuf>>(
+        file_system: &dyn FileSystem,
+        root: P,
+    ) -> Result<Vec<Self>, Error> {
+        fn visit(
+            file_system: &dyn FileSystem,
+            root: PathBuf,
+            loaded: &mut Vec<Project>,
+            visiting: &mut Vec<(PathBuf, String)>,
+        ) -> Result<(), Error> {
+            let project = Project::load_from(file_system, root)?;
+            let key = file_system.canonicalize(&project.root);
+
+            if let Some(start) = visiting.iter().position(|(path, _)| *path == key) {
+                let mut cycle = visiting[start..]
+                    .iter()
+                    .map(|(_, name)| name.clone())
+                    .collect::<Vec<_>>();
+                cycle.push(project.name().to_owned());
+                return Err(Error::DependencyCycle { cycle });
+            }
+
+            if loaded
+                .iter()
+                .any(|other| file_system.canonicalize(&other.root) == key)
+            {
+                return Ok(());
+            }
+
+            visiting.push((key, project.name().to_owned()));
+
+            for dependency in project.manifest.dependencies.values() {
+                visit(
+                    file_system,
+                    project.root.join(&dependency.path),
+                    loaded,
+                    visiting,
+                )?;
+            }
+
+            visiting.pop();
+            loaded.push(project);
+            Ok(())
+        }
+
+        let mut loaded = Vec::new();
+        visit(file_system, root.into(), &mut loaded, &mut Vec::new())?;
+        Ok(loaded)
+    }
+
+    /// Gets the path to the root directory of the project.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Gets the contents of the project's manifest.
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Gets the name of the package.
+    pub fn name(&self) -> &str {
+        &self.manifest.package.name
+    }
+
+    /// Gets the source files of the project, sorted by their paths.
+    pub fn source_files(&self) -> &[SourceFile] {
+        &self.source_files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::limits::Limits;
+    use crate::project::{self, CompilerOptions, Manifest, Project, TargetKind};
+    use crate::vfs::{DiskFileSystem, MemoryFileSystem, Overlay};
+    use std::path::{Path, PathBuf};
+
+    /// Creates an empty directory for a test, removing any files left over from a previous run.
+    fn test_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("lifesharp-project-{}", name));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn write_file(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn manifest_defaults_are_used() {
+        let manifest = Manifest::from_toml("[package]\nname = \"example\"\n").unwrap();
+        assert_eq!("example", manifest.package.name);
+        assert_eq!(TargetKind::Exe, manifest.package.kind);
+        assert_eq!(
+            vec![PathBuf::from("src")],
+            manifest.package.source_directories
+        );
+        assert!(manifest.dependencies.is_empty());
+        assert_eq!(CompilerOptions::default(), manifest.compiler);
+    }
+
+    #[test]
+    fn compiler_options_are_read() {
+        let manifest = Manifest::from_toml(
+            "[package]\nname = \"example\"\n[compiler]\ntarget = \"aarch64-apple-darwin\"\n\
+             [compiler.limits]\nmax-line-length = 120\n",
+        )
+        .unwrap();
+
+        assert_eq!(120, manifest.compiler.limits.max_line_length);
+        assert_eq!(
+            Limits::default().max_nesting_depth,
+            manifest.compiler.limits.max_nesting_depth
+        );
+        assert_eq!(
+            "aarch64-apple-darwin",
+            manifest.compiler.target().unwrap().unwrap().triple
+        );
+    }
+
+    #[test]
+    fn source_files_are_mapped_to_modules() {
+        let root = test_directory("modules");
+        write_file(
+            &root.join("life.toml"),
+            "[package]\nname = \"modules\"\nkind = \"lib\"\n",
+        );
+        write_file(&root.join("src").join("main.lfs"), "");
+        write_file(&root.join("src").join("collections").join("list.lfs"), "");
+        write_file(&root.join("src").join("notes.txt"), "");
+
+        let project = Project::load(&root).unwrap();
+        let modules = project
+            .source_files()
+            .iter()
+            .map(|file| {
+                file.module
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\\")
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(TargetKind::Lib, project.manifest().package.kind);
+        assert_eq!(vec!["collections\\list", "main"], modules);
+    }
+
+    #[test]
+    fn dependencies_are_loaded_first() {
+        let root = test_directory("dependencies");
+        write_file(
+            &root.join("app").join("life.toml"),
+            "[package]\nname = \"app\"\nsource-directories = []\n[dependencies]\nutil = { path = \"../util\" }\n",
+        );
+        write_file(
+            &root.join("util").join("life.toml"),
+            "[package]\nname = \"util\"\nkind = \"lib\"\nsource-directories = []\n",
+        );
+
+        let projects = Project::load_with_dependencies(root.join("app")).unwrap();
+        let names = projects.iter().map(Project::name).collect::<Vec<_>>();
+        assert_eq!(vec!["util", "app"], names);
+    }
+
+    #[test]
+    fn projects_are_loaded_from_memory() {
+        let mut files = MemoryFileSystem::default();
+        files.insert(
+            "app/life.toml",
+            "[package]\nname = \"app\"\n[dependencies]\nutil = { path = \"../util\" }\n",
+        );
+        files.insert("app/src/main.lfs", "");
+        files.insert(
+            "util/life.toml",
+            "[package]\nname = \"util\"\nkind = \"lib\"\n",
+        );
+        files.insert("util/src/strings/format.lfs", "");
+
+        let projects = Project::load_with_dependencies_from(&files, "app").unwrap();
+        let source_files = projects
+            .iter()
+            .flat_map(|project| project.source_files())
+            .map(|file| file.path.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                PathBuf::from("app/../util/src/strings/format.lfs"),
+                PathBuf::from("app/src/main.lfs")
+            ],
+            source_files
+        );
+    }
+
+    #[test]
+    fn dependency_cycle_is_detected() {
+        let root = test_directory("cycle");
+        write_file(
+            &root.join("a").join("life.toml"),
+            "[package]\nname = \"a\"\nsource-directories = []\n[dependencies]\nb = { path = \"../b\" }\n",
+        );
+        write_file(
+            &root.join("b").join("life.toml"),
+            "[package]\nname = \"b\"\nsource-directories = []\n[dependencies]\na = { path = \"../a\" }\n",
+        );
+
+        match Project::load_with_dependencies(root.join("a")) {
+            Err(project::Error::DependencyCycle { cycle }) => {
+                assert_eq!(vec!["a", "b", "a"], cycle)
+            }
+            result => panic!("expected dependency cycle, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn dependency_cycle_is_detected_in_overlay() {
+        let mut overlay = Overlay::new(DiskFileSystem);
+        overlay.changed_mut().insert(
+            "lifesharp-unsaved/a/life.toml",
+            "[package]\nname = \"a\"\nsource-directories = []\n[dependencies]\nb = { path = \"../b\" }\n",
+        );
+        overlay.changed_mut().insert(
+            "lifesharp-unsaved/b/life.toml",
+            "[package]\nname = \"b\"\nsource-directories = []\n[dependencies]\na = { path = \"../a\" }\n",
+        );
+
+        match Project::load_with_dependencies_from(&overlay, "lifesharp-unsaved/a") {
+            Err(project::Error::DependencyCycle { cycle }) => {
+                assert_eq!(vec!["a", "b", "a"], cycle)
+            }
+            result => panic!("expected dependency cycle, got {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Reduction of syntax trees that trigger compiler bugs into smaller trees that still trigger them, which are easier to
+//! turn into bug reports and test cases.
+//!
+//! Trees are reduced one step at a time, by removing declarations, expressions, and parameters, or by replacing an
+//! expression with one of the expressions inside of it. A step is kept only if the reduced tree is still interesting,
+//! for example because compiling it still causes a panic or still reports the same diagnostic.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::ast::{Expression, FunctionDefinition, TopDeclaration, Tree};
+use crate::prelude::*;
+
+/// A single change that makes a tree smaller.
+#[derive(Clone, Debug)]
+enum Reduction {
+    RemoveDeclaration(usize),
+    RemoveExpression(usize, usize),
+    RemoveParameter(usize, usize),
+    RemoveGenericParameter(usize, usize),
+    RemoveReturnType(usize),
+    RemoveUnsafe(usize),
+    /// Replaces an expression in a function body with one of its children. The expression is found by its index in a
+    /// pre-order traversal starting at the expression at the top of the body.
+    Simplify {
+        declaration: usize,
+        expression: usize,
+        node: usize,
+        child: usize,
+    },
+}
+
+/// Gets the ways that an expression can be replaced by one of its children, with the expression itself first and then
+/// the expressions inside of it in the order that they are written.
+///
+/// Each simplification is the index of an expression in a pre-order traversal starting at this expression, followed by the
+/// index of the child that replaces it. The expression is traversed without recursion, so expressions of any depth can be
+/// simplified.
+fn simplifications<'a>(
+    expression: &'a Expression<'_>,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    let mut stack = vec![expression];
+    let mut next_node = 0;
+
+    core::iter::from_fn(move || {
+        let expression = stack.pop()?;
+        let node = next_node;
+        next_node += 1;
+
+        let children = expression.children();
+        let count = children.len();
+        stack.extend(children.into_iter().rev());
+        Some((0..count).map(move |child| (node, child)))
+    })
+    .flatten()
+}
+
+/// Gets every reduction that can be made to a tree, with reductions that remove more of the tree first.
+fn reductions<'a>(tree: &'a Tree<'_>) -> impl Iterator<Item = Reduction> + 'a {
+    let changes = tree
+        .declarations
+        .iter()
+        .enumerate()
+        .flat_map(|(index, declaration)| {
+            let TopDeclaration::FunctionDefinition(definition) = declaration;
+            let expressions = (0..definition.body.len())
+                .map(move |expression| Reduction::RemoveExpression(index, expression));
+            let parameters = (0..definition.parameters.len())
+                .map(move |parameter| Reduction::RemoveParameter(index, parameter));
+            let generic_parameters = (0..definition.generic_parameters.len())
+                .map(move |parameter| Reduction::RemoveGenericParameter(index, parameter));
+            let simplified =
+                definition
+                    .body
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(expression, original)| {
+                        simplifications(&original.content).map(move |(node, child)| {
+                            Reduction::Simplify {
+                                declaration: index,
+                                expression,
+                                node,
+                                child,
+                            }
+                        })
+                    });
+
+            expressions
+                .chain(parameters)
+                .chain(generic_parameters)
+                .chain(
+                    definition
+                        .return_type
+                        .as_ref()
+                        .map(|_| Reduction::RemoveReturnType(index)),
+                )
+                .chain(
+                    definition
+                        .is_unsafe
+                        .then_some(Reduction::RemoveUnsafe(index)),
+                )
+                .chain(simplified)
+        });
+
+    (0..tree.declarations.len())
+        .map(Reduction::RemoveDeclaration)
+        .chain(changes)
+}
+
+fn definition_mut<'a, 't>(tree: &'a mut Tree<'t>, index: usize) -> &'a mut FunctionDefinition<'t> {
+    let TopDeclaration::FunctionDefinition(definition) = &mut tree.declarations[index];
+    definition
+}
+
+/// Makes a copy of a tree with a reduction applied to it.
+fn apply<'t>(tree: &Tree<'t>, reduction: &Reduction) -> Tree<'t> {
+    let mut reduced = tree.clone();
+
+    match reduction {
+        Reduction::RemoveDeclaration(index) => {
+            reduced.declarations.remove(*index);
+        }
+        Reduction::RemoveExpression(index, expression) => {
+            definition_mut(&mut reduced, *index)
+                .body
+                .remove(*expression);
+        }
+        Reduction::RemoveParameter(index, parameter) => {
+            definition_mut(&mut reduced, *index)
+                .parameters
+                .remove(*parameter);
+        }
+        Reduction::RemoveGenericParameter(index, parameter) => {
+            definition_mut(&mut reduced, *index)
+                .generic_parameters
+                .remove(*parameter);
+        }
+        Reduction::RemoveReturnType(index) => {
+            definition_mut(&mut reduced, *index).return_type = None
+        }
+        Reduction::RemoveUnsafe(index) => definition_mut(&mut reduced, *index).is_unsafe = false,
+        Reduction::Simplify {
+            declaration,
+            expression,
+            node,
+            child,
+        } => {
+            let mut stack =
+                vec![&mut definition_mut(&mut reduced, *declaration).body[*expression].content];
+            let mut remaining = *node;
+            let target = loop {
+                let expression = stack.pop().expect("simplified expression should exist");
+                if remaining == 0 {
+                    break expression;
+                }
+
+                remaining -= 1;
+                stack.extend(expression.children_mut().into_iter().rev());
+            };
+
+            *target = target.children()[*child].clone();
+        }
+    }
+
+    reduced
+}
+
+/// Repeatedly reduces a tree for as long as the reduced tree remains interesting, returning the smallest tree that was
+/// found.
+///
+/// The original tree should be interesting. The result is minimal in the sense that no single reduction of it is
+/// interesting, but a smaller interesting tree may still exist. Reduced trees are only created one at a time, as they are
+/// checked.
+pub fn reduce<'t, P: FnMut(&Tree<'t>) -> bool>(tree: &Tree<'t>, mut interesting: P) -> Tree<'t> {
+    let mut current = tree.clone();
+
+    loop {
+        let Some(reduced) = reductions(&current)
+            .map(|reduction| apply(&current, &reduction))
+            .find(|candidate| interesting(candidate))
+        else {
+            return current;
+        };
+
+        current = reduced;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{
+        BinaryOperation, BinaryOperator, Expression, FunctionDefinition, Located, Tree,
+    };
+    use crate::identifier::Id;
+    use crate::reduce;
+
+    fn name(name: &'static str) -> Expression<'static> {
+        Expression::Name(Located::dummy(Id::new(name).unwrap()))
+    }
+
+    fn add(left: Expression<'static>, right: Expression<'static>) -> Expression<'static> {
+        Expression::BinaryOperation(Box::new(BinaryOperation::new(
+            left,
+            Located::dummy(BinaryOperator::Add),
+            right,
+        )))
+    }
+
+    #[test]
+    fn deep_expressions_are_simplified_without_overflowing() {
+        let mut expression = name("a");
+        for _ in 0..10_000 {
+            expression = add(expression, name("b"));
+        }
+
+        let simplifications = reduce::simplifications(&expression);
+        assert_eq!(20_000, simplifications.count());
+        assert_eq!(
+            vec![(0, 0), (0, 1), (1, 0)],
+            reduce::simplifications(&expression)
+                .take(3)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn uninteresting_parts_are_removed() {
+        let mut first = FunctionDefinition::new(Located::dummy(Id::new("f").unwrap()));
+        first.body.push(Located::dummy(name("a")));
+
+        let mut second = FunctionDefinition::new(Located::dummy(Id::new("g").unwrap()));
+        second.is_unsafe = true;
+        second.body.push(Located::dummy(add(
+            name("a"),
+            Expression::Parenthesized(Box::new(Located::dummy(add(
+                name("b"),
+                Expression::Unsafe(vec![Located::dummy(name("c"))]),
+            )))),
+        )));
+        second
+            .body
+            .push(Located::dummy(Expression::BooleanLiteral(true)));
+
+        let tree = Tree {
+            declarations: vec![first.into(), second.into()],
+        };
+
+        let reduced = reduce::reduce(&tree, |candidate| {
+            candidate.to_string().contains("unsafe {")
+        });
+
+        let mut expected = FunctionDefinition::new(Located::dummy(Id::new("g").unwrap()));
+        expected
+            .body
+            .push(Located::dummy(Expression::Unsafe(vec![Located::dummy(
+                name("c"),
+            )])));
+
+        assert_eq!(
+            Tree {
+                declarations: vec![expected.into()]
+            },
+            reduced
+        );
+    }
+}
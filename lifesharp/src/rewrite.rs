@@ -0,0 +1,261 @@
+//! Generation of text edits from changes to the nodes of a syntax tree, used to implement fix suggestions, renaming, and
+//! other refactorings.
+//!
+//! Edits are collected by a [`Rewriter`] and checked when it is finished, so the resulting [`Edits`] never overlap and
+//! always apply cleanly to the original source code.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::ast::Located;
+use crate::cst::{SyntaxNode, SyntaxToken};
+use crate::location::{FileId, Offset, Span};
+use crate::prelude::*;
+use core::ops::Range;
+
+/// Implemented by nodes and tokens that cover a range of the original source code.
+pub trait HasTextRange {
+    /// Gets the span of the source file covered by the node.
+    fn text_range(&self) -> Span;
+}
+
+impl HasTextRange for SyntaxNode {
+    fn text_range(&self) -> Span {
+        SyntaxNode::text_range(self)
+    }
+}
+
+impl HasTextRange for SyntaxToken {
+    fn text_range(&self) -> Span {
+        SyntaxToken::text_range(self)
+    }
+}
+
+impl HasTextRange for Span {
+    fn text_range(&self) -> Span {
+        *self
+    }
+}
+
+impl<T> HasTextRange for Located<T> {
+    fn text_range(&self) -> Span {
+        self.location
+    }
+}
+
+/// Replaces a range of the original source code with new text.
+///
+/// Insertions are represented by edits with an empty range.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct TextEdit {
+    /// The range of byte offsets in the original source code that is replaced.
+    pub range: Range<Offset>,
+    /// The text that the range is replaced with.
+    pub text: String,
+}
+
+/// Error used when two edits change the same part of the source code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct OverlapError {
+    /// The range changed by the edit that starts first.
+    pub first: Range<Offset>,
+    /// The range changed by the edit that starts later.
+    pub second: Range<Offset>,
+}
+
+impl core::fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "edit to {}..{} overlaps edit to {}..{}",
+            self.first.start, self.first.end, self.second.start, self.second.end
+        )
+    }
+}
+
+impl core::error::Error for OverlapError {}
+
+/// Collects edits to the nodes of a single source file.
+///
+/// # Panics
+/// The methods that make edits panic if the node was generated by the compiler, or if it is in a different file than
+/// the nodes that were previously edited.
+#[derive(Debug, Default)]
+pub struct Rewriter {
+    file: Option<FileId>,
+    edits: Vec<TextEdit>,
+}
+
+impl Rewriter {
+    fn edit(&mut self, span: Span, text: impl Into<String>) {
+        assert!(
+            !span.is_synthetic(),
+            "compiler-generated nodes cannot be edited"
+        );
+
+        let file = *self.file.get_or_insert(span.file());
+        assert_eq!(
+            file,
+            span.file(),
+            "edits must all be made to the same source file"
+        );
+
+        self.edits.push(TextEdit {
+            range: span.range(),
+            text: text.into(),
+        })
+    }
+
+    /// Replaces the text of a node.
+    pub fn replace_node<N: HasTextRange + ?Sized>(&mut self, node: &N, text: impl Into<String>) {
+        self.edit(node.text_range(), text)
+    }
+
+    /// Removes the text of a node.
+    pub fn delete_node<N: HasTextRange + ?Sized>(&mut self, node: &N) {
+        self.edit(node.text_range(), String::new())
+    }
+
+    /// Inserts text immediately before a node.
+    pub fn insert_before<N: HasTextRange + ?Sized>(&mut self, node: &N, text: impl Into<String>) {
+        let span = node.text_range();
+        self.edit(Span::new(span.file(), span.start()..span.start()), text)
+    }
+
+    /// Inserts text immediately after a node.
+    pub fn insert_after<N: HasTextRange + ?Sized>(&mut self, node: &N, text: impl Into<String>) {
+        let span = node.text_range();
+        self.edit(Span::new(span.file(), span.end()..span.end()), text)
+    }
+
+    /// Surrounds a node with a prefix and a suffix, such as parentheses.
+    ///
+    /// The node can also be replaced, in which case the prefix and suffix surround the replacement.
+    pub fn wrap_in<N: HasTextRange + ?Sized>(
+        &mut self,
+        node: &N,
+        prefix: impl Into<String>,
+        suffix: impl Into<String>,
+    ) {
+        self.insert_before(node, prefix);
+        self.insert_after(node, suffix);
+    }
+
+    /// Sorts the edits by their position in the source code, checking that no two edits change the same text.
+    ///
+    /// Insertions at the same offset are kept in the order that they were made, and come before any replacement starting
+    /// at that offset.
+    pub fn finish(mut self) -> Result<Edits, OverlapError> {
+        self.edits
+            .sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+        for pair in self.edits.windows(2) {
+            let (first, second) = (&pair[0].range, &pair[1].range);
+            if second.start < first.end {
+                return Err(OverlapError {
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+
+        Ok(Edits {
+            file: self.file,
+            edits: self.edits,
+        })
+    }
+}
+
+/// A sorted list of edits that do not overlap.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Edits {
+    file: Option<FileId>,
+    edits: Vec<TextEdit>,
+}
+
+impl Edits {
+    /// Gets the source file that the edits apply to, or `None` if there are no edits.
+    pub fn file(&self) -> Option<FileId> {
+        self.file
+    }
+
+    /// Gets the edits, sorted by their position in the source code.
+    pub fn as_slice(&self) -> &[TextEdit] {
+        &self.edits
+    }
+
+    /// Applies the edits to the original source code.
+    ///
+    /// # Panics
+    /// Panics if an edit is outside of the source code, or does not start or end on a character boundary.
+    pub fn apply(&self, source: &str) -> String {
+        let mut output = String::with_capacity(source.len());
+        let mut offset = 0;
+
+        for edit in self.edits.iter() {
+            output.push_str(&source[offset..edit.range.start]);
+            output.push_str(&edit.text);
+            offset = edit.range.end;
+        }
+
+        output.push_str(&source[offset..]);
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cst::{Builder, LeafKind, NodeKind, SyntaxNode};
+    use crate::lexer::TokenKind;
+    use crate::location::{FileId, Span};
+    use crate::rewrite::{OverlapError, Rewriter};
+
+    #[test]
+    fn edits_are_applied_in_order() {
+        let source = "a + b * c";
+        let mut builder = Builder::default();
+        builder.start_node(NodeKind::Tree);
+        builder.token(LeafKind::Whitespace, "a + ");
+        builder.start_node(NodeKind::Expression);
+        builder.token(LeafKind::Token(TokenKind::Asterisk), "b * c");
+        builder.finish_node();
+        builder.finish_node();
+
+        let root = SyntaxNode::new_root(builder.finish(), FileId::default());
+        let product = root.child_nodes().next().unwrap();
+
+        let mut rewriter = Rewriter::default();
+        rewriter.replace_node(&product, "x");
+        rewriter.wrap_in(&product, "(", ")");
+        rewriter.insert_before(&Span::new(FileId::default(), 0..1), "-");
+
+        let edits = rewriter.finish().unwrap();
+        assert_eq!(Some(FileId::default()), edits.file());
+        assert_eq!("-a + (x)", edits.apply(source));
+    }
+
+    #[test]
+    #[should_panic(expected = "edits must all be made to the same source file")]
+    fn edits_to_other_files_are_rejected() {
+        let mut rewriter = Rewriter::default();
+        rewriter.delete_node(&Span::new(FileId::new(1), 0..1));
+        rewriter.delete_node(&Span::new(FileId::new(2), 2..3));
+    }
+
+    #[test]
+    fn overlapping_edits_are_rejected() {
+        let file = FileId::default();
+        let mut rewriter = Rewriter::default();
+        rewriter.replace_node(&Span::new(file, 2..5), "x");
+        rewriter.insert_after(&Span::new(file, 0..3), "y");
+
+        assert_eq!(
+            Err(OverlapError {
+                first: 2..5,
+                second: 3..3
+            }),
+            rewriter.finish()
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Counts the tokens and syntax tree nodes of source files, enabled by the `stats` feature.
+//!
+//! The counts are used to analyze collections of source code, and to choose the initial sizes of the buffers used by
+//! the lexer and the interners.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::ast::{Expression, TopDeclaration, Tree};
+use crate::identifier::{Id, Identifier};
+use crate::lexer::{self, TokenKind};
+use std::collections::{BTreeMap, HashMap};
+
+/// Counts of the tokens and nodes in one or more source files.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct Statistics {
+    /// The number of tokens of each kind.
+    pub tokens: HashMap<TokenKind, usize>,
+    /// The number of top-level declarations.
+    pub declarations: usize,
+    /// The number of expressions of each kind, keyed by the name of the kind (e.g. `BinaryOperation`).
+    pub expressions: BTreeMap<&'static str, usize>,
+    /// The greatest number of expressions nested inside each other, where expressions at the top of a function body
+    /// have a depth of one.
+    pub max_nesting_depth: usize,
+    /// The number of times that each name is defined or used.
+    pub identifiers: HashMap<Identifier, usize>,
+}
+
+fn expression_kind(expression: &Expression<'_>) -> &'static str {
+    match expression {
+        Expression::BooleanLiteral(_) => "BooleanLiteral",
+        Expression::IfElse(_) => "IfElse",
+        Expression::BinaryOperation(_) => "BinaryOperation",
+        Expression::UnaryOperation(_) => "UnaryOperation",
+        Expression::Range(_) => "Range",
+        Expression::Block(_) => "Block",
+        Expression::Unsafe(_) => "Unsafe",
+        Expression::Parenthesized(_) => "Parenthesized",
+        Expression::Name(_) => "Name",
+        Expression::Error => "Error",
+    }
+}
+
+impl Statistics {
+    /// Adds the tokens produced by the lexer to the counts.
+    pub fn record_tokens(&mut self, tokens: &lexer::Output<'_>) {
+        for kind in tokens.kinds() {
+            *self.tokens.entry(*kind).or_default() += 1;
+        }
+    }
+
+    fn record_identifier(&mut self, name: &Id) {
+        match self.identifiers.get_mut(name) {
+            Some(count) => *count += 1,
+            None => {
+                self.identifiers.insert(name.to_identifier(), 1);
+            }
+        }
+    }
+
+    /// Adds the declarations and expressions of a syntax tree to the counts.
+    ///
+    /// The tree is traversed without recursion, so trees of any depth can be counted.
+    pub fn record_tree(&mut self, tree: &Tree<'_>) {
+        let mut stack = Vec::<(&Expression<'_>, usize)>::new();
+
+        for declaration in tree.declarations.iter() {
+            self.declarations += 1;
+            match declaration {
+                TopDeclaration::FunctionDefinition(definition) => {
+                    self.record_identifier(definition.name.content);
+                    for parameter in definition.generic_parameters.iter() {
+                        self.record_identifier(parameter.content.name.content);
+                    }
+
+                    for parameter in definition.parameters.iter() {
+                        for name in parameter.content.pattern.bindings() {
+                            self.record_identifier(name.content);
+                        }
+                    }
+
+                    for expression in definition.body.iter() {
+                        stack.push((&expression.content, 1));
+                    }
+                }
+            }
+        }
+
+        while let Some((expression, depth)) = stack.pop() {
+            *self
+                .expressions
+                .entry(expression_kind(expression))
+                .or_default() += 1;
+            self.max_nesting_depth = self.max_nesting_depth.max(depth);
+
+            if let Expression::Name(name) = expression {
+                self.record_identifier(name.content);
+            }
+
+            for child in expression.children() {
+                stack.push((child, depth + 1));
+            }
+        }
+    }
+
+    /// Gets the names that were defined or used most often, along with how many times they appeared, sorted by how
+    /// often they appeared and then by name.
+    pub fn most_common_identifiers(&self, count: usize) -> Vec<(&Id, usize)> {
+        let mut identifiers = self
+            .identifiers
+            .iter()
+            .map(|(name, count)| (name.as_id(), *count))
+            .collect::<Vec<_>>();
+
+        identifiers.sort_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| a_name.as_str().cmp(b_name.as_str()))
+        });
+        identifiers.truncate(count);
+        identifiers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{
+        BinaryOperation, BinaryOperator, Expression, FunctionDefinition, Located, Tree,
+    };
+    use crate::identifier::Id;
+    use crate::lexer::{self, TokenKind};
+    use crate::location::FileId;
+    use crate::stats::Statistics;
+
+    fn name(name: &'static str) -> Expression<'static> {
+        Expression::Name(Located::dummy(Id::new(name).unwrap()))
+    }
+
+    #[test]
+    fn tokens_are_counted_by_kind() {
+        let tokens = lexer::tokenize("((..)", FileId::default(), None).unwrap();
+        let mut statistics = Statistics::default();
+        statistics.record_tokens(&tokens);
+
+        assert_eq!(Some(&2), statistics.tokens.get(&TokenKind::OpenParenthesis));
+        assert_eq!(Some(&1), statistics.tokens.get(&TokenKind::DoublePeriod));
+        assert_eq!(None, statistics.tokens.get(&TokenKind::Semicolon));
+    }
+
+    #[test]
+    fn expressions_and_identifiers_are_counted() {
+        let mut definition = FunctionDefinition::new(Located::dummy(Id::new("a").unwrap()));
+        definition
+            .body
+            .push(Located::dummy(Expression::BinaryOperation(Box::new(
+                BinaryOperation::new(
+                    Expression::Parenthesized(Box::new(Located::dummy(name("a")))),
+                    Located::dummy(BinaryOperator::Add),
+                    name("b"),
+                ),
+            ))));
+        definition
+            .body
+            .push(Located::dummy(Expression::BooleanLiteral(true)));
+
+        let tree = Tree {
+            declarations: vec![definition.into()],
+        };
+
+        let mut statistics = Statistics::default();
+        statistics.record_tree(&tree);
+
+        assert_eq!(1, statistics.declarations);
+        assert_eq!(3, statistics.max_nesting_depth);
+        assert_eq!(Some(&2), statistics.expressions.get("Name"));
+        assert_eq!(Some(&1), statistics.expressions.get("BooleanLiteral"));
+        assert_eq!(
+            vec![("a", 2), ("b", 1)],
+            statistics
+                .most_common_identifiers(5)
+                .into_iter()
+                .map(|(name, count)| (name.as_str(), count))
+                .collect::<Vec<_>>()
+        );
+    }
+}
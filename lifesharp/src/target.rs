@@ -0,0 +1,150 @@
+//! Describes the platforms that LifeSharp code can be compiled for.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use crate::prelude::*;
+
+/// The order of the bytes in integers stored in memory.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Endianness {
+    /// The least significant byte is stored first.
+    Little,
+    /// The most significant byte is stored first.
+    Big,
+}
+
+/// The operating system that compiled code runs on.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Os {
+    /// No operating system, such as for WebAssembly or embedded code.
+    None,
+    /// Linux.
+    Linux,
+    /// macOS.
+    MacOs,
+    /// Windows.
+    Windows,
+}
+
+impl Os {
+    /// Gets the name of the operating system as used in `@cfg(target_os = ...)`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Linux => "linux",
+            Self::MacOs => "macos",
+            Self::Windows => "windows",
+        }
+    }
+}
+
+/// Error used when a target triple is not recognized.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownTargetError(String);
+
+impl core::fmt::Display for UnknownTargetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown target {:?}", self.0)
+    }
+}
+
+impl core::error::Error for UnknownTargetError {}
+
+/// Describes the platform that code is compiled for.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Target {
+    /// The target triple, such as `x86_64-unknown-linux-gnu`.
+    pub triple: String,
+    /// The size of pointers, `ssize`, and `usize`, in bits.
+    pub pointer_width: u32,
+    /// The order of bytes in memory.
+    pub endianness: Endianness,
+    /// The operating system.
+    pub os: Os,
+    /// The size of the C `long` type, in bits, which differs between operating systems with the same pointer width.
+    pub c_long_width: u32,
+}
+
+impl Target {
+    /// Gets the description of a target from its triple (e.g. `aarch64-apple-darwin`).
+    pub fn from_triple(triple: &str) -> Result<Self, UnknownTargetError> {
+        let unknown = || UnknownTargetError(triple.to_owned());
+        let mut components = triple.split('-');
+        let architecture = components.next().ok_or_else(unknown)?;
+
+        let (pointer_width, endianness) = match architecture {
+            "x86_64" | "aarch64" | "riscv64gc" => (64, Endianness::Little),
+            "powerpc64" | "s390x" => (64, Endianness::Big),
+            "i686" | "arm" | "armv7" | "riscv32i" | "wasm32" => (32, Endianness::Little),
+            _ => return Err(unknown()),
+        };
+
+        let components = components.collect::<Vec<_>>();
+        let has = |name| components.contains(&name);
+        let os = if has("linux") {
+            Os::Linux
+        } else if has("darwin") {
+            Os::MacOs
+        } else if has("windows") {
+            Os::Windows
+        } else if has("none") || architecture == "wasm32" {
+            Os::None
+        } else {
+            return Err(unknown());
+        };
+
+        let c_long_width = match os {
+            Os::Windows => 32,
+            _ => pointer_width,
+        };
+
+        Ok(Self {
+            triple: triple.to_owned(),
+            pointer_width,
+            endianness,
+            os,
+            c_long_width,
+        })
+    }
+
+    /// Gets the size of pointers, `ssize`, and `usize`, in bytes.
+    pub fn pointer_size(&self) -> u64 {
+        u64::from(self.pointer_width / 8)
+    }
+}
+
+impl core::str::FromStr for Target {
+    type Err = UnknownTargetError;
+
+    fn from_str(triple: &str) -> Result<Self, UnknownTargetError> {
+        Self::from_triple(triple)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::target::{Endianness, Os, Target};
+
+    #[test]
+    fn triples_are_parsed() {
+        let linux = Target::from_triple("x86_64-unknown-linux-gnu").unwrap();
+        assert_eq!(
+            (64, Os::Linux, 64),
+            (linux.pointer_width, linux.os, linux.c_long_width)
+        );
+
+        let windows = Target::from_triple("x86_64-pc-windows-msvc").unwrap();
+        assert_eq!((Os::Windows, 32), (windows.os, windows.c_long_width));
+
+        let mac = Target::from_triple("aarch64-apple-darwin").unwrap();
+        assert_eq!((Os::MacOs, Endianness::Little), (mac.os, mac.endianness));
+
+        let wasm = Target::from_triple("wasm32-unknown-unknown").unwrap();
+        assert_eq!((32, Os::None), (wasm.pointer_width, wasm.os));
+        assert_eq!(4, wasm.pointer_size());
+
+        assert!(Target::from_triple("m68k-unknown-linux-gnu").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Generators for random source code structures, used for property-based testing.
+
+#![deny(missing_docs)]
+
+use crate::ast::{self, Located};
+use crate::identifier::Id;
+use crate::lexer::Token;
+use crate::types::Primitive;
+use proptest::prelude::*;
+
+/// The names used for generated identifiers, which avoid any keywords.
+pub const NAMES: &[&str] = &["a", "b", "c", "value", "other", "_ignored"];
+
+fn located<T>(content: T) -> Located<T> {
+    Located::dummy(content)
+}
+
+/// Generates identifiers from the list of [`NAMES`].
+pub fn name() -> impl Strategy<Value = ast::Id<'static>> {
+    prop::sample::select(NAMES).prop_map(|name| located(Id::new(name).unwrap()))
+}
+
+/// Generates primitive types.
+pub fn primitive_type() -> impl Strategy<Value = Primitive> {
+    prop::sample::select(&Primitive::ALL[..])
+}
+
+/// Generates binary operators.
+pub fn binary_operator() -> impl Strategy<Value = ast::BinaryOperator> {
+    use ast::BinaryOperator;
+
+    prop::sample::select(
+        &[
+            BinaryOperator::Add,
+            BinaryOperator::Subtract,
+            BinaryOperator::Multiply,
+            BinaryOperator::Divide,
+            BinaryOperator::Equal,
+            BinaryOperator::LessThan,
+            BinaryOperator::GreaterThan,
+            BinaryOperator::And,
+            BinaryOperator::Or,
+            BinaryOperator::BitwiseAnd,
+            BinaryOperator::BitwiseOr,
+            BinaryOperator::BitwiseXor,
+            BinaryOperator::ShiftLeft,
+            BinaryOperator::ShiftRight,
+        ][..],
+    )
+}
+
+fn block<S>(expression: S) -> impl Strategy<Value = ast::Block<'static>>
+where
+    S: Strategy<Value = ast::Expression<'static>>,
+{
+    prop::collection::vec(expression.prop_map(located), 1..3)
+}
+
+/// Generates names, literals, and binary operations on them, without any conditional expressions.
+pub fn operation() -> impl Strategy<Value = ast::Expression<'static>> {
+    let leaf = prop_oneof![
+        any::<bool>().prop_map(ast::Expression::BooleanLiteral),
+        name().prop_map(ast::Expression::Name),
+    ];
+
+    leaf.prop_recursive(4, 32, 2, |operand| {
+        (operand.clone(), binary_operator(), operand).prop_map(|(left, operator, right)| {
+            ast::Expression::BinaryOperation(Box::new(ast::BinaryOperation::new(
+                left,
+                located(operator),
+                right,
+            )))
+        })
+    })
+}
+
+/// Generates expressions of any kind.
+pub fn expression() -> impl Strategy<Value = ast::Expression<'static>> {
+    let leaf = prop_oneof![
+        any::<bool>().prop_map(ast::Expression::BooleanLiteral),
+        name().prop_map(ast::Expression::Name),
+    ];
+
+    leaf.prop_recursive(4, 32, 2, |inner| {
+        prop_oneof![
+            (inner.clone(), binary_operator(), inner.clone()).prop_map(
+                |(left, operator, right)| {
+                    ast::Expression::BinaryOperation(Box::new(ast::BinaryOperation::new(
+                        left,
+                        located(operator),
+                        right,
+                    )))
+                }
+            ),
+            (
+                inner.clone(),
+                block(inner.clone()),
+                prop::collection::vec((inner.clone(), block(inner.clone())), 0..2),
+                block(inner.clone()),
+            )
+                .prop_map(|(condition, true_branch, other_branches, else_branch)| {
+                    ast::Expression::IfElse(Box::new(ast::IfElseExpression {
+                        condition,
+                        true_branch,
+                        other_branches,
+                        else_branch,
+                    }))
+                }),
+            inner.clone().prop_map(|operand| {
+                ast::Expression::UnaryOperation(Box::new(ast::UnaryOperation::new(
+                    located(ast::UnaryOperator::Not),
+                    operand,
+                )))
+            }),
+            (inner.clone(), inner.clone(), any::<bool>()).prop_map(|(start, end, inclusive)| {
+                ast::Expression::Range(Box::new(ast::Range::new(start, end, inclusive)))
+            }),
+            block(inner.clone()).prop_map(ast::Expression::Block),
+            block(inner.clone()).prop_map(ast::Expression::Unsafe),
+            inner.prop_map(|inner| ast::Expression::Parenthesized(Box::new(located(inner)))),
+        ]
+    })
+}
+
+/// Generates parameters that bind a name to an argument of a primitive type.
+pub fn parameter() -> impl Strategy<Value = ast::Parameter<'static>> {
+    (name(), primitive_type()).prop_map(|(name, argument_type)| {
+        let mut parameter = ast::Parameter::new(located(ast::Type::Primitive(argument_type)));
+        parameter.pattern = ast::Pattern::Name(name);
+        parameter
+    })
+}
+
+/// Generates function definitions.
+pub fn function_definition() -> impl Strategy<Value = ast::FunctionDefinition<'static>> {
+    (
+        name(),
+        prop::collection::vec(parameter(), 0..4),
+        prop::option::of(primitive_type()),
+        block(expression()),
+    )
+        .prop_map(|(name, parameters, return_type, body)| {
+            let mut definition = ast::FunctionDefinition::new(name);
+            definition.parameters = parameters.into_iter().map(located).collect();
+            definition.return_type =
+                return_type.map(|primitive| located(ast::Type::Primitive(primitive)));
+            definition.body = body;
+            definition
+        })
+}
+
+/// Generates source files containing function definitions.
+pub fn tree() -> impl Strategy<Value = ast::Tree<'static>> {
+    prop::collection::vec(function_definition(), 0..4).prop_map(|definitions| ast::Tree {
+        declarations: definitions.into_iter().map(Into::into).collect(),
+    })
+}
+
+/// Generates tokens that do not contain any identifiers or literals.
+pub fn simple_token() -> impl Strategy<Value = Token<'static>> {
+    prop::sample::select(
+        &[
+            Token::OpenCurlyBrace,
+            Token::CloseCurlyBrace,
+            Token::OpenParenthesis,
+            Token::CloseParenthesis,
+            Token::OpenSquareBracket,
+            Token::CloseSquareBracket,
+            Token::LessThan,
+            Token::GreaterThan,
+            Token::BackwardSlash,
+            Token::PlusSign,
+            Token::MinusSign,
+            Token::Asterisk,
+            Token::Semicolon,
+            Token::ForwardSlash,
+            Token::Period,
+            Token::Equals,
+            Token::Ampersand,
+            Token::VerticalBar,
+            Token::DoubleAmpersand,
+            Token::DoubleVerticalBar,
+            Token::TripleAmpersand,
+            Token::TripleVerticalBar,
+            Token::TripleCaret,
+            Token::TripleLessThan,
+            Token::TripleGreaterThan,
+        ][..],
+    )
+}
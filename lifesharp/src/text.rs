@@ -0,0 +1,85 @@
+//! Measuring how wide text appears when it is displayed in a terminal or editor with a monospace font.
+//!
+//! Widths are approximated without the full Unicode tables: East Asian wide and fullwidth characters take up two columns,
+//! combining marks, zero-width characters, and control characters take up none, and every other character takes up one.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+/// Ranges of characters that take up two columns, which are the East Asian wide and fullwidth characters and emoji.
+const WIDE: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'),
+    ('\u{2E80}', '\u{303E}'),
+    ('\u{3041}', '\u{33FF}'),
+    ('\u{3400}', '\u{4DBF}'),
+    ('\u{4E00}', '\u{9FFF}'),
+    ('\u{A000}', '\u{A4CF}'),
+    ('\u{AC00}', '\u{D7A3}'),
+    ('\u{F900}', '\u{FAFF}'),
+    ('\u{FE30}', '\u{FE4F}'),
+    ('\u{FF00}', '\u{FF60}'),
+    ('\u{FFE0}', '\u{FFE6}'),
+    ('\u{1F300}', '\u{1F64F}'),
+    ('\u{1F900}', '\u{1F9FF}'),
+    ('\u{20000}', '\u{2FFFD}'),
+    ('\u{30000}', '\u{3FFFD}'),
+];
+
+/// Ranges of characters that are drawn on top of the previous character or are invisible, which take up no columns.
+const ZERO_WIDTH: &[(char, char)] = &[
+    ('\u{0300}', '\u{036F}'),
+    ('\u{200B}', '\u{200F}'),
+    ('\u{20D0}', '\u{20FF}'),
+    ('\u{FE00}', '\u{FE0F}'),
+    ('\u{FE20}', '\u{FE2F}'),
+    ('\u{FEFF}', '\u{FEFF}'),
+];
+
+fn in_ranges(c: char, ranges: &[(char, char)]) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if c < start {
+                core::cmp::Ordering::Greater
+            } else if c > end {
+                core::cmp::Ordering::Less
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Gets the number of columns that a character takes up, which is `0`, `1`, or `2`.
+///
+/// Tabs are control characters, so they have no width here. Use [`display_width`] to measure text containing tabs.
+pub fn char_width(c: char) -> usize {
+    if c.is_control() || in_ranges(c, ZERO_WIDTH) {
+        0
+    } else if in_ranges(c, WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Gets the number of columns that text takes up, where each tab takes up `tab_width` columns.
+pub fn display_width(text: &str, tab_width: usize) -> usize {
+    text.chars()
+        .map(|c| if c == '\t' { tab_width } else { char_width(c) })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::text;
+
+    #[test]
+    fn wide_and_zero_width_characters_are_measured() {
+        assert_eq!(5, text::display_width("hello", 4));
+        assert_eq!(6, text::display_width("名前 a", 4));
+        assert_eq!(4, text::display_width("ｆｕ", 4));
+        assert_eq!(1, text::display_width("e\u{301}", 4));
+        assert_eq!(0, text::display_width("\u{200B}", 4));
+        assert_eq!(2, text::display_width("🦀", 4));
+        assert_eq!(10, text::display_width("\ta\tb", 4));
+    }
+}
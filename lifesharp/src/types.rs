@@ -3,6 +3,7 @@
 #![deny(missing_debug_implementations)]
 
 use crate::print::{self, Print, Printer};
+use crate::target::Target;
 
 /// Represents a primitive type.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -36,9 +37,27 @@ pub enum Primitive {
     F64,
 }
 
-impl Print for Primitive {
-    fn print(&self, printer: &mut Printer) -> print::Result {
-        printer.write_str(match self {
+impl Primitive {
+    /// All of the primitive types.
+    pub const ALL: [Self; 13] = [
+        Self::Bool,
+        Self::S8,
+        Self::U8,
+        Self::S16,
+        Self::U16,
+        Self::S32,
+        Self::U32,
+        Self::S64,
+        Self::U64,
+        Self::SSize,
+        Self::USize,
+        Self::F32,
+        Self::F64,
+    ];
+
+    /// Gets the name of the type, which is also the suffix used in literals of the type (e.g. `10u8`).
+    pub const fn name(self) -> &'static str {
+        match self {
             Self::Bool => "bool",
             Self::S8 => "s8",
             Self::U8 => "u8",
@@ -52,6 +71,113 @@ impl Print for Primitive {
             Self::USize => "usize",
             Self::F32 => "f32",
             Self::F64 => "f64",
+        }
+    }
+
+    /// Gets the primitive type corresponding to a literal suffix, such as the `u32` in `10u32`.
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|primitive| primitive.name() == suffix)
+    }
+
+    /// Gets the number of bits needed to store a value of this type, or `None` for `ssize` and `usize`, whose size
+    /// depends on the target.
+    pub const fn bit_width(self) -> Option<u32> {
+        Some(match self {
+            Self::Bool => 1,
+            Self::S8 | Self::U8 => 8,
+            Self::S16 | Self::U16 => 16,
+            Self::S32 | Self::U32 | Self::F32 => 32,
+            Self::S64 | Self::U64 | Self::F64 => 64,
+            Self::SSize | Self::USize => return None,
         })
     }
+
+    /// Gets the number of bits needed to store a value of this type on the specified target.
+    pub fn target_bit_width(self, target: &Target) -> u32 {
+        self.bit_width().unwrap_or(target.pointer_width)
+    }
+
+    /// Returns `true` if this is an integer type.
+    pub const fn is_integer(self) -> bool {
+        !matches!(self, Self::Bool | Self::F32 | Self::F64)
+    }
+
+    /// Returns `true` if this is a floating-point type.
+    pub const fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+
+    /// Returns `true` if values of this type can be negative.
+    pub const fn is_signed(self) -> bool {
+        matches!(
+            self,
+            Self::S8 | Self::S16 | Self::S32 | Self::S64 | Self::SSize | Self::F32 | Self::F64
+        )
+    }
+
+    /// Gets the smallest value of an integer type, or `None` if this is not an integer type or if its size depends on the
+    /// target.
+    pub const fn min(self) -> Option<i128> {
+        match self.bit_width() {
+            Some(bits) if self.is_integer() && self.is_signed() => Some(-(1 << (bits - 1))),
+            Some(_) if self.is_integer() => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Gets the largest value of an integer type, or `None` if this is not an integer type or if its size depends on the
+    /// target.
+    pub const fn max(self) -> Option<i128> {
+        match self.bit_width() {
+            Some(bits) if self.is_integer() && self.is_signed() => Some((1 << (bits - 1)) - 1),
+            Some(bits) if self.is_integer() => Some((1 << bits) - 1),
+            _ => None,
+        }
+    }
+}
+
+impl Print for Primitive {
+    fn print(&self, printer: &mut Printer) -> print::Result {
+        printer.write_str(self.name())
+    }
+}
+
+crate::print_display_impl!(Primitive);
+
+#[cfg(test)]
+mod tests {
+    use crate::target::Target;
+    use crate::types::Primitive;
+
+    #[test]
+    fn suffix_matches_name() {
+        for primitive in Primitive::ALL {
+            assert_eq!(
+                Some(primitive),
+                Primitive::from_suffix(&primitive.to_string())
+            );
+        }
+
+        assert_eq!(None, Primitive::from_suffix("u128"));
+    }
+
+    #[test]
+    fn integer_bounds_match_rust() {
+        assert_eq!(Some(i8::MIN as i128), Primitive::S8.min());
+        assert_eq!(Some(u16::MAX as i128), Primitive::U16.max());
+        assert_eq!(Some(i64::MAX as i128), Primitive::S64.max());
+        assert_eq!(Some(u64::MAX as i128), Primitive::U64.max());
+        assert_eq!(Some(0), Primitive::U32.min());
+        assert_eq!(None, Primitive::USize.max());
+        assert_eq!(None, Primitive::F64.min());
+    }
+
+    #[test]
+    fn size_types_depend_on_target() {
+        let target = Target::from_triple("wasm32-unknown-unknown").unwrap();
+        assert_eq!(32, Primitive::SSize.target_bit_width(&target));
+        assert_eq!(8, Primitive::U8.target_bit_width(&target));
+    }
 }
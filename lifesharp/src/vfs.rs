@@ -0,0 +1,291 @@
+//! Access to source files through a virtual file system, allowing files to be kept in memory instead of on disk.
+//!
+//! An [`Overlay`] is used by editors to let unsaved buffers shadow the files on disk, while tests can use a
+//! [`MemoryFileSystem`] to compile projects without creating temporary directories.
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Provides read access to files and directories.
+pub trait FileSystem {
+    /// Reads the contents of a file as UTF-8 text.
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    /// Gets the paths of the entries in a directory, sorted by path.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Returns `true` if the path refers to a directory.
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Gets a path that refers to the same file or directory as every other path to it, used to check if two paths are
+    /// the same.
+    ///
+    /// By default, the path is returned unchanged.
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        path.to_owned()
+    }
+}
+
+/// Reads files from the disk.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DiskFileSystem;
+
+impl FileSystem for DiskFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| path.to_owned())
+    }
+}
+
+/// Resolves the `.` and `..` components of a path without accessing any file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Stores the contents of files in memory.
+///
+/// Directories are not stored separately, a path refers to a directory if any file is inside of it. The `.` and `..`
+/// components of paths are resolved before files are looked up.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryFileSystem {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl MemoryFileSystem {
+    /// Sets the contents of a file, returning its previous contents.
+    pub fn insert<P: Into<PathBuf>, S: Into<String>>(
+        &mut self,
+        path: P,
+        contents: S,
+    ) -> Option<String> {
+        self.files.insert(normalize(&path.into()), contents.into())
+    }
+
+    /// Removes a file, returning its contents.
+    pub fn remove(&mut self, path: &Path) -> Option<String> {
+        self.files.remove(&normalize(path))
+    }
+
+    /// Gets the contents of a file, or `None` if the file does not exist.
+    pub fn get(&self, path: &Path) -> Option<&str> {
+        self.files.get(&normalize(path)).map(String::as_str)
+    }
+
+    fn entries(&self, path: &Path) -> Vec<PathBuf> {
+        let directory = normalize(path);
+        let mut entries = Vec::<PathBuf>::new();
+        for file in self.files.keys() {
+            if let Some(name) = file
+                .strip_prefix(&directory)
+                .ok()
+                .and_then(|relative| relative.components().next())
+            {
+                let entry = path.join(name);
+                if entries.last() != Some(&entry) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        entries
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} does not exist", path.display()),
+    )
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.get(path)
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let entries = self.entries(path);
+        if entries.is_empty() {
+            Err(not_found(path))
+        } else {
+            Ok(entries)
+        }
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.get(path).is_none() && !self.entries(path).is_empty()
+    }
+
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        normalize(path)
+    }
+}
+
+/// Reads files from memory if they were changed, and from another file system otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct Overlay<F> {
+    base: F,
+    changed: MemoryFileSystem,
+}
+
+impl<F: FileSystem> Overlay<F> {
+    /// Creates an overlay in which no files are changed.
+    pub fn new(base: F) -> Self {
+        Self {
+            base,
+            changed: MemoryFileSystem::default(),
+        }
+    }
+
+    /// Gets the files that shadow the files in the underlying file system.
+    pub fn changed(&self) -> &MemoryFileSystem {
+        &self.changed
+    }
+
+    /// Gets the files that shadow the files in the underlying file system, allowing them to be changed.
+    pub fn changed_mut(&mut self) -> &mut MemoryFileSystem {
+        &mut self.changed
+    }
+}
+
+impl<F: FileSystem> FileSystem for Overlay<F> {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        match self.changed.get(path) {
+            Some(contents) => Ok(contents.to_owned()),
+            None => self.base.read_to_string(path),
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut entries = self.changed.entries(path);
+        match self.base.read_dir(path) {
+            Ok(base_entries) => entries.extend(base_entries),
+            Err(error) if entries.is_empty() => return Err(error),
+            Err(_) => (),
+        }
+
+        entries.sort();
+        entries.dedup();
+        Ok(entries)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.changed.is_dir(path) || self.base.is_dir(path)
+    }
+
+    /// Changed files and the directories containing them only exist in memory, so their paths are normalized in the same
+    /// way as in a [`MemoryFileSystem`]. Other paths are canonicalized by the underlying file system.
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        if self.changed.get(path).is_some() || self.changed.is_dir(path) {
+            normalize(path)
+        } else {
+            self.base.canonicalize(path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vfs::{DiskFileSystem, FileSystem, MemoryFileSystem, Overlay};
+    use std::path::{Path, PathBuf};
+
+    fn files() -> MemoryFileSystem {
+        let mut files = MemoryFileSystem::default();
+        files.insert("project/src/main.lfs", "main");
+        files.insert("project/src/collections/list.lfs", "list");
+        files.insert("project/life.toml", "manifest");
+        files
+    }
+
+    #[test]
+    fn directories_are_inferred_from_files() {
+        let files = files();
+        assert!(files.is_dir(Path::new("project/src")));
+        assert!(!files.is_dir(Path::new("project/life.toml")));
+        assert_eq!(
+            vec![
+                PathBuf::from("project/src/collections"),
+                PathBuf::from("project/src/main.lfs")
+            ],
+            files.read_dir(Path::new("project/src")).unwrap()
+        );
+        assert!(files.read_dir(Path::new("missing")).is_err());
+    }
+
+    #[test]
+    fn changed_files_shadow_base_files() {
+        let mut overlay = Overlay::new(files());
+        overlay
+            .changed_mut()
+            .insert("project/src/main.lfs", "unsaved");
+        overlay.changed_mut().insert("project/src/new.lfs", "new");
+
+        assert_eq!(
+            "unsaved",
+            overlay
+                .read_to_string(Path::new("project/src/main.lfs"))
+                .unwrap()
+        );
+        assert_eq!(
+            "list",
+            overlay
+                .read_to_string(Path::new("project/src/collections/list.lfs"))
+                .unwrap()
+        );
+        assert_eq!(3, overlay.read_dir(Path::new("project/src")).unwrap().len());
+    }
+
+    #[test]
+    fn changed_paths_are_normalized() {
+        let mut overlay = Overlay::new(DiskFileSystem);
+        overlay
+            .changed_mut()
+            .insert("lifesharp-unsaved/a/life.toml", "");
+
+        assert_eq!(
+            PathBuf::from("lifesharp-unsaved/a"),
+            overlay.canonicalize(Path::new("lifesharp-unsaved/b/../a"))
+        );
+        assert_eq!(
+            PathBuf::from("lifesharp-unsaved/a/life.toml"),
+            overlay.canonicalize(Path::new("lifesharp-unsaved/./a/life.toml"))
+        );
+    }
+}
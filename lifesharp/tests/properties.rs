@@ -0,0 +1,127 @@
+//! Property-based tests using the generators in `lifesharp::testing`.
+
+use lifesharp::ast::{Associativity, BinaryOperation, BinaryOperator, Expression, Located};
+use lifesharp::identifier::Id;
+use lifesharp::location::FileId;
+use lifesharp::{float, lexer, print, reduce, testing};
+use proptest::prelude::*;
+
+/// Parses an expression containing names, boolean literals, binary operators, and parentheses, using the same precedence
+/// and associativity rules as the printer.
+///
+/// Stands in for the parser until one exists.
+struct OperationParser<'s> {
+    words: Vec<&'s str>,
+    index: usize,
+}
+
+impl<'s> OperationParser<'s> {
+    fn new(source: &'s str) -> Self {
+        Self {
+            words: source.split_whitespace().collect(),
+            index: 0,
+        }
+    }
+
+    fn next_word(&mut self) -> &'s str {
+        let word = self.words[self.index];
+        self.index += 1;
+        word
+    }
+
+    fn operator(word: &str) -> Option<BinaryOperator> {
+        Some(match word {
+            "+" => BinaryOperator::Add,
+            "-" => BinaryOperator::Subtract,
+            "*" => BinaryOperator::Multiply,
+            "/" => BinaryOperator::Divide,
+            "=" => BinaryOperator::Equal,
+            "<" => BinaryOperator::LessThan,
+            ">" => BinaryOperator::GreaterThan,
+            "&&" => BinaryOperator::And,
+            "||" => BinaryOperator::Or,
+            "&&&" => BinaryOperator::BitwiseAnd,
+            "|||" => BinaryOperator::BitwiseOr,
+            "^^^" => BinaryOperator::BitwiseXor,
+            "<<<" => BinaryOperator::ShiftLeft,
+            ">>>" => BinaryOperator::ShiftRight,
+            _ => return None,
+        })
+    }
+
+    fn operand(&mut self) -> Expression<'s> {
+        match self.next_word() {
+            "(" => {
+                let expression = self.expression(0);
+                assert_eq!(")", self.next_word());
+                expression
+            }
+            "true" => Expression::BooleanLiteral(true),
+            "false" => Expression::BooleanLiteral(false),
+            name => Expression::Name(Located::dummy(Id::new(name).unwrap())),
+        }
+    }
+
+    fn expression(&mut self, minimum_precedence: u8) -> Expression<'s> {
+        let mut left = self.operand();
+
+        while let Some(operator) = self
+            .words
+            .get(self.index)
+            .and_then(|word| Self::operator(word))
+            .filter(|operator| operator.precedence() >= minimum_precedence)
+        {
+            self.index += 1;
+            let next_precedence = match operator.associativity() {
+                Associativity::Left => operator.precedence() + 1,
+                Associativity::Right => operator.precedence(),
+            };
+
+            let right = self.expression(next_precedence);
+            left = Expression::BinaryOperation(Box::new(BinaryOperation::new(
+                left,
+                Located::dummy(operator),
+                right,
+            )));
+        }
+
+        left
+    }
+}
+
+proptest! {
+    #[test]
+    fn printed_tokens_are_tokenized_again(tokens in prop::collection::vec(testing::simple_token(), 0..32)) {
+        let source = tokens.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n");
+        let output = lexer::tokenize(source.as_str(), FileId::default(), None).unwrap();
+        let tokenized = output.tokens().map(|(token, _)| token).collect::<Vec<_>>();
+        prop_assert_eq!(tokens, tokenized);
+    }
+
+    #[test]
+    fn printed_operation_is_parsed_again(expression in testing::operation()) {
+        let printed = print::to_string(&expression)
+            .replace('(', " ( ")
+            .replace(')', " ) ");
+        let mut parser = OperationParser::new(&printed);
+        prop_assert_eq!(&expression, &parser.expression(0));
+        prop_assert_eq!(parser.index, parser.words.len());
+    }
+
+    #[test]
+    fn trees_are_reduced_to_nothing(tree in testing::tree()) {
+        prop_assert!(reduce::reduce(&tree, |_| true).declarations.is_empty());
+        prop_assert_eq!(&tree, &reduce::reduce(&tree, |_| false));
+    }
+
+    #[test]
+    fn formatted_float_is_parsed_again(value in any::<f64>().prop_filter("finite", |value| value.is_finite())) {
+        match float::format_f64(value) {
+            Ok(literal) => prop_assert_eq!(value.to_bits(), float::parse_f64(&literal).unwrap().to_bits()),
+            Err(error) => {
+                prop_assert!(value.is_sign_negative());
+                prop_assert_eq!(float::FormatError::Negative, error);
+            }
+        }
+    }
+}
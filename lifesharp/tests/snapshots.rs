@@ -0,0 +1,78 @@
+//! Compares the output of each stage of the compiler for the sample files in `tests/snapshots` against the expected output
+//! checked in next to them.
+//!
+//! Set the `LIFESHARP_UPDATE_SNAPSHOTS` environment variable to write the current output as the new expected output.
+
+use lifesharp::location::FileId;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+const UPDATE_VARIABLE: &str = "LIFESHARP_UPDATE_SNAPSHOTS";
+
+/// Gets the paths to the sample source files.
+fn sample_files() -> Vec<PathBuf> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots");
+    let mut samples = std::fs::read_dir(directory)
+        .expect("snapshot directory should be readable")
+        .map(|entry| {
+            entry
+                .expect("snapshot directory entry should be readable")
+                .path()
+        })
+        .filter(|path| path.extension() == Some(OsStr::new("lfs")))
+        .collect::<Vec<_>>();
+
+    samples.sort();
+    samples
+}
+
+/// Compares the actual output with the contents of the expected output file, returning a description of the mismatch if
+/// they differ.
+fn check_snapshot(expected_path: &Path, actual: &str, update: bool) -> Result<(), String> {
+    if update {
+        std::fs::write(expected_path, actual)
+            .map_err(|error| format!("could not update {}: {}", expected_path.display(), error))?;
+        return Ok(());
+    }
+
+    match std::fs::read_to_string(expected_path) {
+        Ok(expected) if expected == actual => Ok(()),
+        Ok(expected) => Err(format!(
+            "{} does not match\n--- expected\n{}\n--- actual\n{}",
+            expected_path.display(),
+            expected,
+            actual
+        )),
+        Err(error) => Err(format!(
+            "could not read {} ({}), set {} to create it",
+            expected_path.display(),
+            error,
+            UPDATE_VARIABLE
+        )),
+    }
+}
+
+#[test]
+fn snapshots_match() {
+    let update = std::env::var_os(UPDATE_VARIABLE).is_some();
+    let samples = sample_files();
+    let mut failures = Vec::new();
+
+    assert!(!samples.is_empty(), "no sample files were found");
+
+    for sample in samples.iter() {
+        let source = std::fs::read_to_string(sample).expect("sample file should be readable");
+        let tokens = lifesharp::lexer::tokenize(source.as_str(), FileId::default(), None).unwrap();
+        let mut listing = String::new();
+        lifesharp::print::write_token_listing(&mut listing, &tokens).unwrap();
+
+        // TODO: Compare the parsed and printed tree once a parser exists.
+        if let Err(failure) = check_snapshot(&sample.with_extension("tokens"), &listing, update) {
+            failures.push(failure);
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}